@@ -0,0 +1,51 @@
+//! Serialize generated descriptors into a region and feed it back through the parsers. Parsing must never panic, and
+//! anything accepted must decode to exactly what was written.
+//!
+//! Run with `cargo +nightly fuzz run round_trip` from the repository root.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use ec_slimloader_descriptors::{
+    AppImageDescriptor, BootableRegionDescriptorHeader, BootableRegionDescriptors, APP_IMAGE_DESCRIPTOR_SIZE,
+    BOOT_REGION_DESCRIPTOR_SIZE,
+};
+use libfuzzer_sys::fuzz_target;
+
+const SLOTS: usize = 4;
+const REGION_SIZE: usize = BOOT_REGION_DESCRIPTOR_SIZE + SLOTS * APP_IMAGE_DESCRIPTOR_SIZE;
+
+#[derive(Debug, Arbitrary)]
+struct Region {
+    header: BootableRegionDescriptorHeader,
+    apps: [AppImageDescriptor; SLOTS],
+}
+
+fuzz_target!(|input: Region| {
+    let mut region = [0u8; REGION_SIZE];
+    input.header.write_to(&mut region).unwrap();
+    for (slot, app) in input.apps.iter().enumerate() {
+        app.write_to(&mut region[BOOT_REGION_DESCRIPTOR_SIZE + slot * APP_IMAGE_DESCRIPTOR_SIZE..])
+            .unwrap();
+    }
+
+    if let Ok(header) = BootableRegionDescriptorHeader::from_bytes(&region) {
+        assert_eq!(header, input.header);
+    }
+    for (slot, app) in input.apps.iter().enumerate() {
+        if let Ok(parsed) =
+            AppImageDescriptor::from_bytes(&region[BOOT_REGION_DESCRIPTOR_SIZE + slot * APP_IMAGE_DESCRIPTOR_SIZE..])
+        {
+            assert_eq!(parsed, *app);
+        }
+    }
+
+    if let Ok(descriptors) = BootableRegionDescriptors::from_bytes(&region) {
+        // only a region whose descriptors start right after the header reads back the apps in the order written
+        if { input.header.app_descriptor_base_address } == BOOT_REGION_DESCRIPTOR_SIZE as u32 {
+            for (slot, app) in descriptors.iter().enumerate() {
+                assert_eq!(app.unwrap(), input.apps[slot]);
+            }
+        }
+    }
+});
@@ -0,0 +1,669 @@
+//! 64-bit address variant of the bootable region descriptors, for parts with flash or RAM mapped above 4GB.
+//!
+//! The layout mirrors BootableRegionDescriptorHeader and AppImageDescriptor with every address and size widened to u64.
+//! The header carries BOOT_REGION_DESCRIPTOR64_SIGNATURE instead of BOOT_REGION_DESCRIPTOR_SIGNATURE, so a bootloader
+//! only expecting 32-bit descriptors rejects a 64-bit region with InvalidSignature rather than misparsing it.
+//!
+//! The 64-bit layout is frozen. Fields added to the 32-bit format since (reserved_bytes, sequence, boot_attempts, name and
+//! integrity_type) are not part of it, so 64-bit images are always checked with CRC32, and boot attempt tracking and
+//! redundant regions are only available with 32-bit descriptors.
+
+use core::mem::size_of;
+
+use bytemuck::{Pod, Zeroable};
+use constmuck::bytes_of;
+use crc::Algorithm;
+
+use crate::{
+    checksum_without_crc, is_erased, write_bytes, CrcChecked, DescriptorHeader, Endianness, ImageDescriptor,
+    ParseError, SizeField, APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS, DESCRIPTOR_CRC_ALGORITHM, DESCRIPTOR_VERSION,
+};
+
+/// Magic number for finding or aligning 64-bit bootable region descriptors header
+pub const BOOT_REGION_DESCRIPTOR64_SIGNATURE: u32 = 0x6464_2222;
+
+/// Size of the DESCRIPTOR_VERSION of the 64-bit bootable region descriptors header
+pub const BOOT_REGION_DESCRIPTOR64_SIZE: usize = size_of::<BootableRegionDescriptorHeader64>();
+
+/// Size of the DESCRIPTOR_VERSION of the 64-bit bootable region app image descriptor
+pub const APP_IMAGE_DESCRIPTOR64_SIZE: usize = size_of::<AppImageDescriptor64>();
+
+// on-flash layout is part of the descriptor format, catch accidental field changes at compile time
+const _: () = assert!(BOOT_REGION_DESCRIPTOR64_SIZE == 36);
+const _: () = assert!(APP_IMAGE_DESCRIPTOR64_SIZE == 64);
+
+// CRC32 checksums are computed over whole u32 words
+const _: () = assert!(BOOT_REGION_DESCRIPTOR64_SIZE % size_of::<u32>() == 0);
+const _: () = assert!(APP_IMAGE_DESCRIPTOR64_SIZE % size_of::<u32>() == 0);
+
+/// The descriptor region header for regions whose app descriptors may live above 4GB
+#[repr(C, packed)]
+#[derive(Copy, Clone, Debug, Zeroable, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BootableRegionDescriptorHeader64 {
+    /// BOOT_REGION_DESCRIPTOR64_SIGNATURE
+    pub signature: u32,
+
+    /// DESCRIPTOR_VERSION in the format h'MM_mmmm_pp
+    pub descriptor_version: u32,
+
+    /// BOOT_REGION_DESCRIPTOR64_SIZE
+    pub descriptor_header_size_bytes: u32,
+
+    /// APP_IMAGE_DESCRIPTOR64_SIZE
+    pub app_descriptor_size_bytes: u32,
+
+    /// Readable address where AppImageDescriptor64\[num_active_slots\] is placed
+    pub app_descriptor_base_address: u64,
+
+    /// The number of AppImageDescriptor64's in the bootable descriptor region
+    pub num_app_slots: u32,
+
+    /// Corresponds to which AppImageDescriptor64 should be booted
+    pub active_app_slot: u32,
+
+    /// CRC32 checksum of above parameters
+    pub header_crc: u32,
+}
+
+/// The App Image Descriptor for images stored or executed above 4GB
+#[repr(C, packed)]
+#[derive(Copy, Clone, Debug, Zeroable, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AppImageDescriptor64 {
+    /// DESCRIPTOR_VERSION in the format h'MM_mmmm_pp
+    pub descriptor_version: u32,
+
+    /// Corresponds to index in AppImageDescriptor64\[BootableRegionDescriptorHeader64::num_app_slots\]
+    pub app_slot_number: u32,
+
+    /// Application version for handling recovery and roll forward or back behaviors
+    pub app_version: u32,
+
+    /// Security version corresponding to this application image for roll-back attack protection enablement
+    pub security_version: u32,
+
+    /// App image behavior flags, shared with AppImageDescriptor
+    pub flags: u32,
+
+    /// Where the full, contiguous app image is stored
+    pub stored_address: u64,
+
+    /// The size of the app image stored at stored_address
+    pub image_size_bytes: u64,
+
+    /// The address where the CRC32 checksum over stored_address through stored_address + image_size_bytes is kept
+    pub stored_crc_address: u64,
+
+    /// how much memory to move from stored_address to execution_address before performing app load from bootloader
+    pub execution_copy_size_bytes: u64,
+
+    /// where to begin execution once the app image is validated and loaded
+    pub execution_address: u64,
+
+    /// CRC32 checksum over the above parameters
+    pub descriptor_crc: u32,
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for BootableRegionDescriptorHeader64 {
+    fn format(&self, fmt: defmt::Formatter) {
+        // copy out of the packed struct, defmt can't take references to unaligned fields
+        let Self {
+            signature,
+            descriptor_version,
+            descriptor_header_size_bytes,
+            app_descriptor_size_bytes,
+            app_descriptor_base_address,
+            num_app_slots,
+            active_app_slot,
+            header_crc,
+        } = *self;
+
+        defmt::write!(
+            fmt,
+            "BootableRegionDescriptorHeader64 {{ signature: {=u32:#x}, descriptor_version: {=u32:#x}, descriptor_header_size_bytes: {=u32}, app_descriptor_size_bytes: {=u32}, app_descriptor_base_address: {=u64:#x}, num_app_slots: {=u32}, active_app_slot: {=u32}, header_crc: {=u32:#x} }}",
+            signature,
+            descriptor_version,
+            descriptor_header_size_bytes,
+            app_descriptor_size_bytes,
+            app_descriptor_base_address,
+            num_app_slots,
+            active_app_slot,
+            header_crc,
+        );
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for AppImageDescriptor64 {
+    fn format(&self, fmt: defmt::Formatter) {
+        let Self {
+            descriptor_version,
+            app_slot_number,
+            app_version,
+            security_version,
+            flags,
+            stored_address,
+            image_size_bytes,
+            stored_crc_address,
+            execution_copy_size_bytes,
+            execution_address,
+            descriptor_crc,
+        } = *self;
+
+        defmt::write!(
+            fmt,
+            "AppImageDescriptor64 {{ descriptor_version: {=u32:#x}, app_slot_number: {=u32}, app_version: {=u32:#x}, security_version: {=u32}, flags: {=u32:#x}, stored_address: {=u64:#x}, image_size_bytes: {=u64}, stored_crc_address: {=u64:#x}, execution_copy_size_bytes: {=u64}, execution_address: {=u64:#x}, descriptor_crc: {=u32:#x} }}",
+            descriptor_version,
+            app_slot_number,
+            app_version,
+            security_version,
+            flags,
+            stored_address,
+            image_size_bytes,
+            stored_crc_address,
+            execution_copy_size_bytes,
+            execution_address,
+            descriptor_crc,
+        );
+    }
+}
+
+impl BootableRegionDescriptorHeader64 {
+    /// Generate at compile time a 64-bit descriptor region header.
+    /// header_crc is computed with CRC_32_ISO_HDLC.
+    pub const fn new(
+        app_slot_count: u32,
+        active_app_slot: u32,
+        app_descriptor_address: u64,
+    ) -> BootableRegionDescriptorHeader64 {
+        let mut this = BootableRegionDescriptorHeader64 {
+            signature: BOOT_REGION_DESCRIPTOR64_SIGNATURE,
+            descriptor_version: DESCRIPTOR_VERSION,
+            descriptor_header_size_bytes: BOOT_REGION_DESCRIPTOR64_SIZE as u32,
+            app_descriptor_size_bytes: APP_IMAGE_DESCRIPTOR64_SIZE as u32,
+            app_descriptor_base_address: app_descriptor_address,
+            num_app_slots: app_slot_count,
+            active_app_slot,
+            header_crc: 0,
+        };
+
+        this.header_crc = this.compute_crc();
+
+        this
+    }
+
+    /// Parse and validate a 64-bit header from the start of buf, in the on-flash (little endian) format
+    pub fn from_bytes(buf: &[u8]) -> Result<BootableRegionDescriptorHeader64, ParseError> {
+        BootableRegionDescriptorHeader64::from_bytes_endian(buf, Endianness::ON_FLASH)
+    }
+
+    /// Parse and validate a 64-bit header encoded in byte order endian from the start of buf
+    pub fn from_bytes_endian(buf: &[u8], endian: Endianness) -> Result<BootableRegionDescriptorHeader64, ParseError> {
+        let bytes = buf
+            .get(..BOOT_REGION_DESCRIPTOR64_SIZE)
+            .ok_or(ParseError::BufferTooSmall {
+                needed: BOOT_REGION_DESCRIPTOR64_SIZE,
+                got: buf.len(),
+            })?;
+        let mut unvalidated: BootableRegionDescriptorHeader64 = bytemuck::pod_read_unaligned(bytes);
+        if endian.swaps() {
+            unvalidated = unvalidated.byte_swapped();
+        }
+
+        if is_erased(bytes) {
+            Err(ParseError::ErasedRegion)
+        } else if unvalidated.signature != BOOT_REGION_DESCRIPTOR64_SIGNATURE {
+            Err(ParseError::InvalidSignature)
+        } else if !unvalidated.is_crc_valid() {
+            Err(ParseError::InvalidHeaderCrc {
+                found: unvalidated.header_crc,
+                expected: unvalidated.compute_crc(),
+            })
+        } else if unvalidated.descriptor_header_size_bytes != BOOT_REGION_DESCRIPTOR64_SIZE as u32 {
+            Err(ParseError::SizeMismatch {
+                field: SizeField::DescriptorHeaderSize,
+                found: unvalidated.descriptor_header_size_bytes,
+                expected: BOOT_REGION_DESCRIPTOR64_SIZE as u32,
+            })
+        } else if unvalidated.app_descriptor_size_bytes != APP_IMAGE_DESCRIPTOR64_SIZE as u32 {
+            Err(ParseError::SizeMismatch {
+                field: SizeField::AppDescriptorSize,
+                found: unvalidated.app_descriptor_size_bytes,
+                expected: APP_IMAGE_DESCRIPTOR64_SIZE as u32,
+            })
+        } else if unvalidated.num_app_slots < 1 {
+            Err(ParseError::InvalidSlotCount)
+        } else if unvalidated.active_app_slot >= unvalidated.num_app_slots {
+            Err(ParseError::InvalidAppSlot)
+        } else {
+            Ok(unvalidated)
+        }
+    }
+
+    /// Return this struct's contents as a slice
+    pub const fn as_bytes(&self) -> &[u8] {
+        bytes_of(self)
+    }
+
+    /// Write this header into the start of buf in the on-flash (little endian) format, returning the number of bytes written
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        self.write_to_endian(buf, Endianness::ON_FLASH)
+    }
+
+    /// Write this header encoded in byte order endian into the start of buf, returning the number of bytes written
+    pub fn write_to_endian(&self, buf: &mut [u8], endian: Endianness) -> Result<usize, ParseError> {
+        let encoded = if endian.swaps() { self.byte_swapped() } else { *self };
+        write_bytes(encoded.as_bytes(), buf)
+    }
+
+    /// This header with every field in little endian byte order
+    const fn to_le(self) -> BootableRegionDescriptorHeader64 {
+        if cfg!(target_endian = "big") {
+            self.byte_swapped()
+        } else {
+            self
+        }
+    }
+
+    /// This header with every field byte swapped
+    const fn byte_swapped(self) -> BootableRegionDescriptorHeader64 {
+        BootableRegionDescriptorHeader64 {
+            signature: self.signature.swap_bytes(),
+            descriptor_version: self.descriptor_version.swap_bytes(),
+            descriptor_header_size_bytes: self.descriptor_header_size_bytes.swap_bytes(),
+            app_descriptor_size_bytes: self.app_descriptor_size_bytes.swap_bytes(),
+            app_descriptor_base_address: self.app_descriptor_base_address.swap_bytes(),
+            num_app_slots: self.num_app_slots.swap_bytes(),
+            active_app_slot: self.active_app_slot.swap_bytes(),
+            header_crc: self.header_crc.swap_bytes(),
+        }
+    }
+
+    /// Return the CRC32 checksum over the current contents of this struct, as encoded on flash (little endian)
+    pub const fn compute_crc(&self) -> u32 {
+        self.compute_crc_with(&DESCRIPTOR_CRC_ALGORITHM)
+    }
+
+    /// Return the checksum under algorithm over the current contents of this header, as encoded on flash (little endian)
+    pub const fn compute_crc_with(&self, algorithm: &'static Algorithm<u32>) -> u32 {
+        checksum_without_crc(bytes_of(&self.to_le()), algorithm)
+    }
+
+    /// Check if the header_crc value matches the current computed CRC32 checksum
+    pub const fn is_crc_valid(&self) -> bool {
+        self.compute_crc() == self.header_crc
+    }
+}
+
+impl AppImageDescriptor64 {
+    /// Generate a non-copied (XIP: execute in place) 64-bit app image descriptor with the given parameters.
+    /// descriptor_crc is computed with CRC_32_ISO_HDLC.
+    pub const fn new_execute_in_place_image(
+        slot: u32,
+        app_version: u32,
+        security_version: u32,
+        flags: u32,
+        stored_address: u64,
+        image_size_bytes: u64,
+        stored_crc_address: u64,
+    ) -> AppImageDescriptor64 {
+        let mut app_image_descriptor = Self {
+            descriptor_version: DESCRIPTOR_VERSION,
+            app_slot_number: slot,
+            app_version,
+            security_version,
+            flags,
+            stored_address,
+            image_size_bytes,
+            stored_crc_address,
+            execution_address: stored_address,
+            execution_copy_size_bytes: 0,
+            descriptor_crc: 0,
+        };
+
+        app_image_descriptor.descriptor_crc = app_image_descriptor.compute_crc();
+
+        app_image_descriptor
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Generate a copied to RAM 64-bit app image descriptor with given parameters.
+    /// descriptor_crc is computed with CRC_32_ISO_HDLC.
+    pub const fn new_ram_image(
+        slot: u32,
+        app_version: u32,
+        security_version: u32,
+        flags: u32,
+        flash_address: u64,
+        image_size_bytes: u64,
+        ram_address: u64,
+        stored_crc_address: u64,
+    ) -> AppImageDescriptor64 {
+        let mut app_image_descriptor = Self {
+            descriptor_version: DESCRIPTOR_VERSION,
+            app_slot_number: slot,
+            app_version,
+            security_version,
+            flags: flags | APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS,
+            stored_address: flash_address,
+            image_size_bytes,
+            stored_crc_address,
+            execution_address: ram_address,
+            execution_copy_size_bytes: image_size_bytes,
+            descriptor_crc: 0,
+        };
+
+        app_image_descriptor.descriptor_crc = app_image_descriptor.compute_crc();
+
+        app_image_descriptor
+    }
+
+    /// Parse and validate a 64-bit app image descriptor from the start of buf, in the on-flash (little endian) format
+    pub fn from_bytes(buf: &[u8]) -> Result<AppImageDescriptor64, ParseError> {
+        AppImageDescriptor64::from_bytes_endian(buf, Endianness::ON_FLASH)
+    }
+
+    /// Parse and validate a 64-bit app image descriptor encoded in byte order endian from the start of buf
+    pub fn from_bytes_endian(buf: &[u8], endian: Endianness) -> Result<AppImageDescriptor64, ParseError> {
+        let bytes = buf
+            .get(..APP_IMAGE_DESCRIPTOR64_SIZE)
+            .ok_or(ParseError::BufferTooSmall {
+                needed: APP_IMAGE_DESCRIPTOR64_SIZE,
+                got: buf.len(),
+            })?;
+        let mut unvalidated: AppImageDescriptor64 = bytemuck::pod_read_unaligned(bytes);
+        if endian.swaps() {
+            unvalidated = unvalidated.byte_swapped();
+        }
+
+        if !unvalidated.is_crc_valid() {
+            return Err(ParseError::InvalidAppCrc {
+                slot: unvalidated.app_slot_number,
+                address: buf.as_ptr() as usize,
+                found: unvalidated.descriptor_crc,
+                expected: unvalidated.compute_crc(),
+            });
+        }
+        unvalidated.validate_ranges()?;
+
+        Ok(unvalidated)
+    }
+
+    /// Return this struct's contents as a slice
+    pub const fn as_bytes(&self) -> &[u8] {
+        bytes_of(self)
+    }
+
+    /// Write this descriptor into the start of buf in the on-flash (little endian) format, returning the number of bytes written
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        self.write_to_endian(buf, Endianness::ON_FLASH)
+    }
+
+    /// Write this descriptor encoded in byte order endian into the start of buf, returning the number of bytes written
+    pub fn write_to_endian(&self, buf: &mut [u8], endian: Endianness) -> Result<usize, ParseError> {
+        let encoded = if endian.swaps() { self.byte_swapped() } else { *self };
+        write_bytes(encoded.as_bytes(), buf)
+    }
+
+    /// This descriptor with every field in little endian byte order
+    const fn to_le(self) -> AppImageDescriptor64 {
+        if cfg!(target_endian = "big") {
+            self.byte_swapped()
+        } else {
+            self
+        }
+    }
+
+    /// This descriptor with every field byte swapped
+    const fn byte_swapped(self) -> AppImageDescriptor64 {
+        AppImageDescriptor64 {
+            descriptor_version: self.descriptor_version.swap_bytes(),
+            app_slot_number: self.app_slot_number.swap_bytes(),
+            app_version: self.app_version.swap_bytes(),
+            security_version: self.security_version.swap_bytes(),
+            flags: self.flags.swap_bytes(),
+            stored_address: self.stored_address.swap_bytes(),
+            image_size_bytes: self.image_size_bytes.swap_bytes(),
+            stored_crc_address: self.stored_crc_address.swap_bytes(),
+            execution_copy_size_bytes: self.execution_copy_size_bytes.swap_bytes(),
+            execution_address: self.execution_address.swap_bytes(),
+            descriptor_crc: self.descriptor_crc.swap_bytes(),
+        }
+    }
+
+    /// Return the CRC32 checksum over the current contents of this struct, as encoded on flash (little endian)
+    pub const fn compute_crc(&self) -> u32 {
+        self.compute_crc_with(&DESCRIPTOR_CRC_ALGORITHM)
+    }
+
+    /// Return the checksum under algorithm over the current contents of this descriptor, as encoded on flash (little endian)
+    pub const fn compute_crc_with(&self, algorithm: &'static Algorithm<u32>) -> u32 {
+        checksum_without_crc(bytes_of(&self.to_le()), algorithm)
+    }
+
+    /// Check if the descriptor_crc value matches the current computed CRC32 checksum
+    pub const fn is_crc_valid(&self) -> bool {
+        self.compute_crc() == self.descriptor_crc
+    }
+}
+
+impl PartialEq for BootableRegionDescriptorHeader64 {
+    /// Byte-wise comparison of every field, including header_crc
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for BootableRegionDescriptorHeader64 {}
+
+impl PartialEq for AppImageDescriptor64 {
+    /// Byte-wise comparison of every field, including descriptor_crc
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for AppImageDescriptor64 {}
+
+impl core::hash::Hash for BootableRegionDescriptorHeader64 {
+    /// Hashes the same bytes PartialEq compares
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl core::hash::Hash for AppImageDescriptor64 {
+    /// Hashes the same bytes PartialEq compares
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl CrcChecked for BootableRegionDescriptorHeader64 {
+    const SIZE: usize = BOOT_REGION_DESCRIPTOR64_SIZE;
+
+    fn to_le(self) -> Self {
+        BootableRegionDescriptorHeader64::to_le(self)
+    }
+}
+
+impl CrcChecked for AppImageDescriptor64 {
+    const SIZE: usize = APP_IMAGE_DESCRIPTOR64_SIZE;
+
+    fn to_le(self) -> Self {
+        AppImageDescriptor64::to_le(self)
+    }
+}
+
+impl DescriptorHeader for BootableRegionDescriptorHeader64 {
+    const SIGNATURE: u32 = BOOT_REGION_DESCRIPTOR64_SIGNATURE;
+
+    type App = AppImageDescriptor64;
+
+    fn app_descriptor_base_address(&self) -> u64 {
+        self.app_descriptor_base_address
+    }
+
+    fn num_app_slots(&self) -> u32 {
+        self.num_app_slots
+    }
+
+    fn active_app_slot(&self) -> u32 {
+        self.active_app_slot
+    }
+}
+
+impl ImageDescriptor for AppImageDescriptor64 {
+    const ADDRESS_MAX: u64 = u64::MAX;
+
+    fn app_slot_number(&self) -> u32 {
+        self.app_slot_number
+    }
+
+    fn app_version(&self) -> u32 {
+        self.app_version
+    }
+
+    fn security_version(&self) -> u32 {
+        self.security_version
+    }
+
+    fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    fn stored_address(&self) -> u64 {
+        self.stored_address
+    }
+
+    fn image_size_bytes(&self) -> u64 {
+        self.image_size_bytes
+    }
+
+    fn stored_crc_address(&self) -> u64 {
+        self.stored_crc_address
+    }
+
+    fn execution_copy_size_bytes(&self) -> u64 {
+        self.execution_copy_size_bytes
+    }
+
+    fn execution_address(&self) -> u64 {
+        self.execution_address
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{AppImageDescriptor, BootableRegionDescriptorHeader, APP_IMAGE_FLAG_NONE, BOOT_REGION_DESCRIPTOR_SIZE};
+
+    #[test]
+    fn round_trip_above_4gb() {
+        let header = BootableRegionDescriptorHeader64::new(2, 1, 0x1_0000_0000);
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR64_SIZE];
+        assert_eq!(header.write_to(&mut buf).unwrap(), BOOT_REGION_DESCRIPTOR64_SIZE);
+        let parsed = BootableRegionDescriptorHeader64::from_bytes(&buf).unwrap();
+        assert_eq!({ parsed.app_descriptor_base_address }, 0x1_0000_0000);
+        assert_eq!({ parsed.active_app_slot }, 1);
+
+        let app = AppImageDescriptor64::new_ram_image(
+            1,
+            2,
+            0,
+            APP_IMAGE_FLAG_NONE,
+            0x2_0000_0000,
+            0x1_0000,
+            0x3_0000_0000,
+            0x2_0001_0000,
+        );
+        let mut buf = [0u8; APP_IMAGE_DESCRIPTOR64_SIZE];
+        assert_eq!(app.write_to(&mut buf).unwrap(), APP_IMAGE_DESCRIPTOR64_SIZE);
+        let parsed = AppImageDescriptor64::from_bytes(&buf).unwrap();
+        assert_eq!(parsed, app);
+        assert_eq!({ parsed.execution_address }, 0x3_0000_0000);
+        assert_eq!(parsed.execution_end(), Some(0x3_0001_0000));
+        assert!(parsed.is_copy_to_execution());
+    }
+
+    #[test]
+    fn explicit_endianness_round_trip() {
+        let header = BootableRegionDescriptorHeader64::new(2, 1, 0x1_0000_0000);
+        let app = AppImageDescriptor64::new_execute_in_place_image(
+            1,
+            2,
+            0,
+            APP_IMAGE_FLAG_NONE,
+            0x2_0000_0000,
+            0x1_0000,
+            0x2_0001_0000,
+        );
+
+        for endian in [Endianness::Little, Endianness::Big] {
+            let mut buf = [0u8; BOOT_REGION_DESCRIPTOR64_SIZE];
+            header.write_to_endian(&mut buf, endian).unwrap();
+            assert_eq!(
+                BootableRegionDescriptorHeader64::from_bytes_endian(&buf, endian).unwrap(),
+                header
+            );
+
+            let mut buf = [0u8; APP_IMAGE_DESCRIPTOR64_SIZE];
+            app.write_to_endian(&mut buf, endian).unwrap();
+            assert_eq!(AppImageDescriptor64::from_bytes_endian(&buf, endian).unwrap(), app);
+        }
+
+        // decoding with the wrong byte order fails the CRC check rather than misinterpreting fields
+        let mut be = [0u8; APP_IMAGE_DESCRIPTOR64_SIZE];
+        app.write_to_endian(&mut be, Endianness::Big).unwrap();
+        assert!(matches!(
+            AppImageDescriptor64::from_bytes(&be),
+            Err(ParseError::InvalidAppCrc { .. })
+        ));
+    }
+
+    #[test]
+    fn formats_are_not_interchangeable() {
+        let header64 = BootableRegionDescriptorHeader64::new(1, 0, 0x1_0000_0000);
+        // the 32-bit header is now the larger of the two, so both fit
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        header64.write_to(&mut buf).unwrap();
+        assert!(matches!(
+            BootableRegionDescriptorHeader::from_bytes(&buf),
+            Err(ParseError::InvalidSignature)
+        ));
+
+        let header = BootableRegionDescriptorHeader::new(1, 0, 0x1000);
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        header.write_to(&mut buf).unwrap();
+        assert!(matches!(
+            BootableRegionDescriptorHeader64::from_bytes(&buf),
+            Err(ParseError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn generic_over_descriptor_width() {
+        fn boot_address<H: DescriptorHeader>(header: &H, app: &H::App) -> Option<u64> {
+            (header.is_crc_valid() && app.is_crc_valid() && app.validate_ranges().is_ok())
+                .then(|| app.execution_address())
+        }
+
+        let header = BootableRegionDescriptorHeader::new(1, 0, 0x1000);
+        let app = AppImageDescriptor::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0x1000_0000, 0x1000, 0);
+        assert_eq!(boot_address(&header, &app), Some(0x1000_0000));
+
+        let header = BootableRegionDescriptorHeader64::new(1, 0, 0x1_0000_0000);
+        let app =
+            AppImageDescriptor64::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0x4_0000_0000, 0x1000, 0);
+        assert_eq!(boot_address(&header, &app), Some(0x4_0000_0000));
+
+        // a 32-bit image overflowing 4GB is rejected even though it fits in the widened u64
+        let app = AppImageDescriptor::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0xFFFF_F000, 0x2000, 0);
+        assert!(matches!(
+            ImageDescriptor::validate_ranges(&app),
+            Err(ParseError::AddressOverflow { slot: 0 })
+        ));
+    }
+}
@@ -0,0 +1,295 @@
+//! Field by field comparison of two descriptor regions, for verifying a region after it's been flashed.
+//!
+//! A single CRC over the region only says whether it changed. After an update the active slot and CRCs are expected to
+//! differ from the previous region, and anything else differing points at a bad write. diff_regions reports exactly which
+//! fields differ, so update tooling can check the differences are the intended ones.
+
+use crate::{AppImageDescriptor, BootableRegionDescriptorHeader, ParseError, APP_IMAGE_DESCRIPTOR_SIZE};
+
+/// Which header fields differ between two regions
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HeaderDiff {
+    /// signature differs
+    pub signature: bool,
+
+    /// descriptor_version differs
+    pub descriptor_version: bool,
+
+    /// descriptor_header_size_bytes differs
+    pub descriptor_header_size_bytes: bool,
+
+    /// app_descriptor_size_bytes differs
+    pub app_descriptor_size_bytes: bool,
+
+    /// app_descriptor_base_address differs
+    pub app_descriptor_base_address: bool,
+
+    /// num_app_slots differs
+    pub num_app_slots: bool,
+
+    /// active_app_slot differs
+    pub active_app_slot: bool,
+
+    /// reserved_bytes differs
+    pub reserved_bytes: bool,
+
+    /// sequence differs
+    pub sequence: bool,
+
+    /// header_crc differs
+    pub header_crc: bool,
+}
+
+impl HeaderDiff {
+    /// Compare every field of expected and actual
+    pub fn between(expected: &BootableRegionDescriptorHeader, actual: &BootableRegionDescriptorHeader) -> Self {
+        Self {
+            signature: expected.signature != actual.signature,
+            descriptor_version: expected.descriptor_version != actual.descriptor_version,
+            descriptor_header_size_bytes: expected.descriptor_header_size_bytes != actual.descriptor_header_size_bytes,
+            app_descriptor_size_bytes: expected.app_descriptor_size_bytes != actual.app_descriptor_size_bytes,
+            app_descriptor_base_address: expected.app_descriptor_base_address != actual.app_descriptor_base_address,
+            num_app_slots: expected.num_app_slots != actual.num_app_slots,
+            active_app_slot: expected.active_app_slot != actual.active_app_slot,
+            reserved_bytes: expected.reserved_bytes != actual.reserved_bytes,
+            sequence: expected.sequence != actual.sequence,
+            header_crc: expected.header_crc != actual.header_crc,
+        }
+    }
+
+    /// Check if no field differs
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Which app image descriptor fields differ between the same slot of two regions
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AppDiff {
+    /// descriptor_version differs
+    pub descriptor_version: bool,
+
+    /// app_slot_number differs
+    pub app_slot_number: bool,
+
+    /// app_version differs
+    pub app_version: bool,
+
+    /// security_version differs
+    pub security_version: bool,
+
+    /// flags differs
+    pub flags: bool,
+
+    /// stored_address differs
+    pub stored_address: bool,
+
+    /// image_size_bytes differs
+    pub image_size_bytes: bool,
+
+    /// stored_crc_address differs
+    pub stored_crc_address: bool,
+
+    /// execution_copy_size_bytes differs
+    pub execution_copy_size_bytes: bool,
+
+    /// execution_address differs
+    pub execution_address: bool,
+
+    /// boot_attempts differs
+    pub boot_attempts: bool,
+
+    /// name differs
+    pub name: bool,
+
+    /// integrity_type differs
+    pub integrity_type: bool,
+
+    /// descriptor_crc differs
+    pub descriptor_crc: bool,
+}
+
+impl AppDiff {
+    /// Compare every field of expected and actual
+    pub fn between(expected: &AppImageDescriptor, actual: &AppImageDescriptor) -> Self {
+        Self {
+            descriptor_version: expected.descriptor_version != actual.descriptor_version,
+            app_slot_number: expected.app_slot_number != actual.app_slot_number,
+            app_version: expected.app_version != actual.app_version,
+            security_version: expected.security_version != actual.security_version,
+            flags: expected.flags != actual.flags,
+            stored_address: expected.stored_address != actual.stored_address,
+            image_size_bytes: expected.image_size_bytes != actual.image_size_bytes,
+            stored_crc_address: expected.stored_crc_address != actual.stored_crc_address,
+            execution_copy_size_bytes: expected.execution_copy_size_bytes != actual.execution_copy_size_bytes,
+            execution_address: expected.execution_address != actual.execution_address,
+            boot_attempts: expected.boot_attempts != actual.boot_attempts,
+            name: expected.name != actual.name,
+            integrity_type: expected.integrity_type != actual.integrity_type,
+            descriptor_crc: expected.descriptor_crc != actual.descriptor_crc,
+        }
+    }
+
+    /// Check if no field differs
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Differences between two regions laid out as for BootableRegionDescriptors::from_bytes. Nothing is validated, so a
+/// region with bad CRCs or an out of range active slot can still be compared.
+#[derive(Copy, Clone, Debug)]
+pub struct RegionDiff<'a> {
+    expected: &'a [u8],
+    actual: &'a [u8],
+    expected_header: BootableRegionDescriptorHeader,
+    actual_header: BootableRegionDescriptorHeader,
+}
+
+impl RegionDiff<'_> {
+    /// Which header fields differ
+    pub fn header(&self) -> HeaderDiff {
+        HeaderDiff::between(&self.expected_header, &self.actual_header)
+    }
+
+    /// Number of app slots present in both regions, the slots app can compare
+    pub fn common_slots(&self) -> u32 {
+        self.expected_header.num_app_slots.min(self.actual_header.num_app_slots)
+    }
+
+    /// Which fields of app descriptor slot differ, each read from where its own region's header places it. Fails with
+    /// InvalidAppSlot if either region has no such slot, or BufferTooSmall if a region ends before the descriptor.
+    pub fn app(&self, slot: u32) -> Result<AppDiff, ParseError> {
+        let expected = read_app(self.expected, &self.expected_header, slot)?;
+        let actual = read_app(self.actual, &self.actual_header, slot)?;
+
+        Ok(AppDiff::between(&expected, &actual))
+    }
+
+    /// Check if the regions are identical apart from which slot is active and the CRCs that covers: what a successful
+    /// activation of an already written slot looks like
+    pub fn only_active_slot_differs(&self) -> Result<bool, ParseError> {
+        let header = HeaderDiff {
+            active_app_slot: false,
+            header_crc: false,
+            ..self.header()
+        };
+        if !header.is_empty() {
+            return Ok(false);
+        }
+
+        for slot in 0..self.common_slots() {
+            if !self.app(slot)?.is_empty() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Compare two regions laid out as for BootableRegionDescriptors::from_bytes field by field, typically the region update
+/// tooling meant to write against the one read back from the device. Only fails if either region is too short to hold a
+/// header.
+pub fn diff_regions<'a>(expected: &'a [u8], actual: &'a [u8]) -> Result<RegionDiff<'a>, ParseError> {
+    Ok(RegionDiff {
+        expected,
+        actual,
+        expected_header: BootableRegionDescriptorHeader::read_unchecked(expected)?,
+        actual_header: BootableRegionDescriptorHeader::read_unchecked(actual)?,
+    })
+}
+
+/// Decode app descriptor slot of region without validating it
+fn read_app(
+    region: &[u8],
+    header: &BootableRegionDescriptorHeader,
+    slot: u32,
+) -> Result<AppImageDescriptor, ParseError> {
+    let offset = header.descriptor_offset(slot)?;
+    let bytes = offset
+        .checked_add(APP_IMAGE_DESCRIPTOR_SIZE)
+        .and_then(|end| region.get(offset..end))
+        .ok_or(ParseError::BufferTooSmall {
+            needed: offset.saturating_add(APP_IMAGE_DESCRIPTOR_SIZE),
+            got: region.len(),
+        })?;
+
+    // to_le is its own inverse, so it also decodes from the on-flash format
+    Ok(bytemuck::pod_read_unaligned::<AppImageDescriptor>(bytes).to_le())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::{build_region, APP_IMAGE_FLAG_NONE, BOOT_REGION_DESCRIPTOR_SIZE};
+
+    const REGION_SIZE: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+
+    fn region(active: u32, apps: &[AppImageDescriptor; 2]) -> [u8; REGION_SIZE] {
+        build_region(
+            BootableRegionDescriptorHeader::new(2, active, BOOT_REGION_DESCRIPTOR_SIZE as u32),
+            apps,
+        )
+    }
+
+    fn apps() -> [AppImageDescriptor; 2] {
+        [
+            AppImageDescriptor::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0x1000, 0x100, 0x1100),
+            AppImageDescriptor::new_execute_in_place_image(1, 2, 0, APP_IMAGE_FLAG_NONE, 0x2000, 0x100, 0x2100),
+        ]
+    }
+
+    #[test]
+    fn active_slot_change() {
+        let expected = region(1, &apps());
+        let actual = region(0, &apps());
+        let diff = diff_regions(&expected, &actual).unwrap();
+
+        assert_eq!(
+            diff.header(),
+            HeaderDiff {
+                active_app_slot: true,
+                header_crc: true,
+                ..HeaderDiff::default()
+            }
+        );
+        assert_eq!(diff.common_slots(), 2);
+        assert!(diff.app(0).unwrap().is_empty());
+        assert!(diff.app(1).unwrap().is_empty());
+        assert!(matches!(diff.app(2), Err(ParseError::InvalidAppSlot)));
+        assert!(diff.only_active_slot_differs().unwrap());
+
+        assert!(diff_regions(&expected, &expected).unwrap().header().is_empty());
+    }
+
+    #[test]
+    fn app_field_differs() {
+        let mut changed = apps();
+        changed[1].app_version = 3;
+        changed[1].recompute_crc();
+        let expected = region(1, &apps());
+        let actual = region(1, &changed);
+        let diff = diff_regions(&expected, &actual).unwrap();
+
+        assert!(diff.header().is_empty());
+        assert!(diff.app(0).unwrap().is_empty());
+        assert_eq!(
+            diff.app(1).unwrap(),
+            AppDiff {
+                app_version: true,
+                descriptor_crc: true,
+                ..AppDiff::default()
+            }
+        );
+        assert!(!diff.only_active_slot_differs().unwrap());
+
+        assert!(matches!(
+            diff_regions(&expected, &actual[..BOOT_REGION_DESCRIPTOR_SIZE - 1]),
+            Err(ParseError::BufferTooSmall { .. })
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+//! Typed wrapper around the APP_IMAGE_FLAG_* bits of AppImageDescriptor::flags.
+
+use core::ops::{BitAnd, BitOr};
+
+use crate::{
+    APP_IMAGE_FLAG_ALL, APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS, APP_IMAGE_FLAG_ENCRYPTED, APP_IMAGE_FLAG_NONE,
+    APP_IMAGE_FLAG_RECOVERY, APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+};
+
+/// Set of app image flags. Converts to and from the raw u32 stored in AppImageDescriptor::flags, keeping any unknown bits.
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AppImageFlags(u32);
+
+impl AppImageFlags {
+    /// APP_IMAGE_FLAG_NONE
+    pub const NONE: Self = Self(APP_IMAGE_FLAG_NONE);
+
+    /// APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS
+    pub const COPY_TO_EXECUTION_ADDRESS: Self = Self(APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS);
+
+    /// APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK
+    pub const SKIP_IMAGE_CRC_CHECK: Self = Self(APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK);
+
+    /// APP_IMAGE_FLAG_RECOVERY
+    pub const RECOVERY: Self = Self(APP_IMAGE_FLAG_RECOVERY);
+
+    /// APP_IMAGE_FLAG_ENCRYPTED
+    pub const ENCRYPTED: Self = Self(APP_IMAGE_FLAG_ENCRYPTED);
+
+    /// APP_IMAGE_FLAG_ALL
+    pub const ALL: Self = Self(APP_IMAGE_FLAG_ALL);
+
+    /// Wrap raw flag bits, including any this crate doesn't know about
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// The raw flag bits, as stored in AppImageDescriptor::flags
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Check if no flags are set
+    pub const fn is_empty(self) -> bool {
+        self.0 == APP_IMAGE_FLAG_NONE
+    }
+
+    /// Check if every flag in other is also set in self
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Flags set in either self or other
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Flags set in both self and other
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Set every flag in other
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clear every flag in other
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl BitOr for AppImageFlags {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+impl BitAnd for AppImageFlags {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        self.intersection(other)
+    }
+}
+
+impl From<u32> for AppImageFlags {
+    fn from(bits: u32) -> Self {
+        Self::from_bits(bits)
+    }
+}
+
+impl From<AppImageFlags> for u32 {
+    fn from(flags: AppImageFlags) -> Self {
+        flags.bits()
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::AppImageDescriptorBuilder;
+
+    #[test]
+    fn union_and_intersection() {
+        let flags = AppImageFlags::COPY_TO_EXECUTION_ADDRESS | AppImageFlags::ENCRYPTED;
+        assert!(flags.contains(AppImageFlags::ENCRYPTED));
+        assert!(!flags.contains(AppImageFlags::RECOVERY));
+        assert!(AppImageFlags::ALL.contains(flags));
+
+        assert_eq!(flags & AppImageFlags::ENCRYPTED, AppImageFlags::ENCRYPTED);
+        assert!((flags & AppImageFlags::RECOVERY).is_empty());
+
+        let mut flags = flags;
+        flags.remove(AppImageFlags::ENCRYPTED);
+        flags.insert(AppImageFlags::SKIP_IMAGE_CRC_CHECK);
+        assert_eq!(
+            flags,
+            AppImageFlags::COPY_TO_EXECUTION_ADDRESS | AppImageFlags::SKIP_IMAGE_CRC_CHECK
+        );
+    }
+
+    #[test]
+    fn round_trip_u32() {
+        let raw = APP_IMAGE_FLAG_RECOVERY | 0x8000_0000;
+        let flags = AppImageFlags::from(raw);
+        assert!(flags.contains(AppImageFlags::RECOVERY));
+        assert_eq!(u32::from(flags), raw);
+
+        // typed and raw flags build the same descriptor
+        let typed = AppImageDescriptorBuilder::new().flags(AppImageFlags::RECOVERY).build();
+        let untyped = AppImageDescriptorBuilder::new().flags(APP_IMAGE_FLAG_RECOVERY).build();
+        assert_eq!(typed, untyped);
+        assert_eq!(typed.image_flags(), AppImageFlags::RECOVERY);
+    }
+}
@@ -0,0 +1,93 @@
+//! Structured generation of descriptors for fuzzing the parsers, behind the arbitrary feature.
+//!
+//! Fully random bytes almost never get past the signature and CRC checks, so the generated descriptors carry the expected
+//! signature, version, sizes and a correct CRC most of the time and an arbitrary value otherwise. That steers the fuzzer into
+//! the field validation behind those checks while still covering the rejection paths.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    AppImageDescriptor, BootableRegionDescriptorHeader, APP_IMAGE_DESCRIPTOR_SIZE, BOOT_REGION_DESCRIPTOR_SIGNATURE,
+    BOOT_REGION_DESCRIPTOR_SIZE, DESCRIPTOR_VERSION, INTEGRITY_CRC32,
+};
+
+/// valid most of the time, otherwise any u32
+fn mostly(u: &mut Unstructured<'_>, valid: u32) -> Result<u32> {
+    if u.ratio(7, 8)? {
+        Ok(valid)
+    } else {
+        u.arbitrary()
+    }
+}
+
+impl<'a> Arbitrary<'a> for BootableRegionDescriptorHeader {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut header = Self {
+            signature: mostly(u, BOOT_REGION_DESCRIPTOR_SIGNATURE)?,
+            descriptor_version: mostly(u, DESCRIPTOR_VERSION)?,
+            descriptor_header_size_bytes: mostly(u, BOOT_REGION_DESCRIPTOR_SIZE as u32)?,
+            app_descriptor_size_bytes: mostly(u, APP_IMAGE_DESCRIPTOR_SIZE as u32)?,
+            app_descriptor_base_address: u.arbitrary()?,
+            num_app_slots: u.arbitrary()?,
+            active_app_slot: u.arbitrary()?,
+            reserved_bytes: u.arbitrary()?,
+            sequence: u.arbitrary()?,
+            header_crc: u.arbitrary()?,
+        };
+        if u.ratio(7, 8)? {
+            header.recompute_crc();
+        }
+
+        Ok(header)
+    }
+}
+
+impl<'a> Arbitrary<'a> for AppImageDescriptor {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut descriptor = Self {
+            descriptor_version: mostly(u, DESCRIPTOR_VERSION)?,
+            app_slot_number: u.arbitrary()?,
+            app_version: u.arbitrary()?,
+            security_version: u.arbitrary()?,
+            flags: u.arbitrary()?,
+            stored_address: u.arbitrary()?,
+            image_size_bytes: u.arbitrary()?,
+            stored_crc_address: u.arbitrary()?,
+            execution_copy_size_bytes: u.arbitrary()?,
+            execution_address: u.arbitrary()?,
+            boot_attempts: u.arbitrary()?,
+            name: u.arbitrary()?,
+            integrity_type: mostly(u, INTEGRITY_CRC32)?,
+            descriptor_crc: u.arbitrary()?,
+        };
+        if u.ratio(7, 8)? {
+            descriptor.recompute_crc();
+        }
+
+        Ok(descriptor)
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn zeroed_input_takes_valid_choices() {
+        // a zeroed input takes the valid choice at every ratio
+        let data = [0u8; 256];
+        let mut u = Unstructured::new(&data);
+
+        let header = BootableRegionDescriptorHeader::arbitrary(&mut u).unwrap();
+        assert!(header.is_crc_valid());
+        assert_eq!({ header.signature }, BOOT_REGION_DESCRIPTOR_SIGNATURE);
+        assert_eq!(
+            { header.descriptor_header_size_bytes },
+            BOOT_REGION_DESCRIPTOR_SIZE as u32
+        );
+
+        let descriptor = AppImageDescriptor::arbitrary(&mut u).unwrap();
+        assert!(descriptor.is_crc_valid());
+        assert_eq!({ descriptor.descriptor_version }, DESCRIPTOR_VERSION);
+    }
+}
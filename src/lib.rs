@@ -12,15 +12,43 @@
 //! 4. Depending on image flags, bootloader may perform a CRC integrity check over the app image in place
 //! 5. Depending on image flags, the bootloader may copy the validated image from stored_image_address to execution_image_address
 //! 6. If everything has been validated and copied, the bootloader will set the VTOR and main stack pointer, then branch to the reset vector
+//!
+//! ## byte order
+//!
+//! Descriptors are always stored on flash in little endian byte order, regardless of the host that builds or reads them. CRC32 checksums are computed over that little endian encoding. from_bytes/write_to and the pointer based constructors use the on-flash format (Endianness::ON_FLASH), from_bytes_endian/write_to_endian take any Endianness for both the 32-bit and 64-bit layouts, and as_bytes returns the host's in-memory representation (Endianness::NATIVE).
 
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::fmt;
+use core::marker::PhantomData;
 use core::mem::size_of;
 
 use bytemuck::{Pod, Zeroable};
 use constmuck::bytes_of;
 /// re-export for matching software CRC32 checksum
-pub use crc::{Crc, Digest, CRC_32_ISO_HDLC};
+pub use crc::{Algorithm, Crc, Digest, CRC_32_ISO_HDLC};
+
+mod descriptor64;
+pub use descriptor64::*;
+
+mod diff;
+pub use diff::{diff_regions, AppDiff, HeaderDiff, RegionDiff};
+
+mod flags;
+pub use flags::AppImageFlags;
+
+#[cfg(feature = "arbitrary")]
+mod fuzzing;
+
+mod reader;
+use reader::ErasedFlashRead;
+pub use reader::{FlashRead, FlashWrite};
+
+mod update;
+pub use update::UpdateTransaction;
 
 mod version {
     include!(concat!(env!("OUT_DIR"), "/version.rs"));
@@ -29,13 +57,82 @@ mod version {
 pub const DESCRIPTOR_VERSION: u32 = version::CRATE_VERSION;
 
 /// Just the major field of the descriptor version
-pub const DESCRIPTOR_VERSION_MAJOR: u32 = (DESCRIPTOR_VERSION >> 24) & 0xFF;
+pub const DESCRIPTOR_VERSION_MAJOR: u32 = version_parts(DESCRIPTOR_VERSION).0 as u32;
 
 /// Just the minor field of the descriptor version
-pub const DESCRIPTOR_VERSION_MINOR: u32 = (DESCRIPTOR_VERSION >> 8) & 0xFFFF;
+pub const DESCRIPTOR_VERSION_MINOR: u32 = version_parts(DESCRIPTOR_VERSION).1 as u32;
 
 /// Just the patch field of the descriptor version
-pub const DESCRIPTOR_VERSION_PATCH: u32 = DESCRIPTOR_VERSION & 0xFF;
+pub const DESCRIPTOR_VERSION_PATCH: u32 = version_parts(DESCRIPTOR_VERSION).2 as u32;
+
+/// Split a version word in the format h'MM_mmmm_pp into (major, minor, patch). Used by descriptor_version, and by
+/// app_version when tooling follows the same convention.
+pub const fn version_parts(version: u32) -> (u8, u16, u8) {
+    ((version >> 24) as u8, (version >> 8) as u16, version as u8)
+}
+
+/// Build a version word in the format h'MM_mmmm_pp from its parts, the inverse of version_parts
+pub const fn make_version(major: u8, minor: u16, patch: u8) -> u32 {
+    (major as u32) << 24 | (minor as u32) << 8 | patch as u32
+}
+
+/// A version word in the format h'MM_mmmm_pp split into its fields. Orders by major, then minor, then patch.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DescriptorVersion {
+    /// MM, changes when the descriptor layout changes incompatibly
+    pub major: u8,
+    /// mmmm
+    pub minor: u16,
+    /// pp
+    pub patch: u8,
+}
+
+impl DescriptorVersion {
+    /// The descriptor format version this crate reads and writes, DESCRIPTOR_VERSION
+    pub const CURRENT: DescriptorVersion = DescriptorVersion::from_u32(DESCRIPTOR_VERSION);
+
+    /// Split a version word, see version_parts
+    pub const fn from_u32(version: u32) -> Self {
+        let (major, minor, patch) = version_parts(version);
+        Self { major, minor, patch }
+    }
+
+    /// Pack back into a version word, see make_version
+    pub const fn to_u32(self) -> u32 {
+        make_version(self.major, self.minor, self.patch)
+    }
+
+    /// Check if descriptors of version other can be interpreted by code written for self, following semver: the major
+    /// fields match, and while major is 0 the minor fields match too, as every 0.x minor release may change the layout.
+    /// Patch differences are always allowed.
+    pub const fn is_compatible_with(&self, other: &DescriptorVersion) -> bool {
+        self.major == other.major && (self.major != 0 || self.minor == other.minor)
+    }
+}
+
+impl From<u32> for DescriptorVersion {
+    fn from(version: u32) -> Self {
+        Self::from_u32(version)
+    }
+}
+
+impl From<DescriptorVersion> for u32 {
+    fn from(version: DescriptorVersion) -> Self {
+        version.to_u32()
+    }
+}
+
+impl fmt::Display for DescriptorVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// CRC32 algorithm (polynomial, init, reflection and xorout) used for header_crc and descriptor_crc, for external tools
+/// that generate descriptors without linking this crate
+pub const DESCRIPTOR_CRC_ALGORITHM: Algorithm<u32> = CRC_32_ISO_HDLC;
 
 /// Magic number for finding or aligning bootable region descriptors header
 pub const BOOT_REGION_DESCRIPTOR_SIGNATURE: u32 = 0x2222_2222;
@@ -43,6 +140,11 @@ pub const BOOT_REGION_DESCRIPTOR_SIGNATURE: u32 = 0x2222_2222;
 /// Size of the DESCRIPTOR_VERSION iteration of the bootable region descriptors header
 pub const BOOT_REGION_DESCRIPTOR_SIZE: usize = size_of::<BootableRegionDescriptorHeader>();
 
+/// Upper bound on num_app_slots accepted by the memory mapped and reader constructors, well above any real EC layout, so a
+/// corrupted header can't drive an overflow or a long validation loop. from_address_bounded and from_bytes_bounded bound
+/// the scan themselves instead.
+pub const MAX_REASONABLE_SLOTS: u32 = 64;
+
 /// App Image Flags: No flags set
 pub const APP_IMAGE_FLAG_NONE: u32 = 0x0000_0000;
 
@@ -52,12 +154,71 @@ pub const APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS: u32 = 0x0000_0001;
 /// App Image Flags: Skip CRC32 checksum integrity check on app image corresponding to app image descriptor
 pub const APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK: u32 = 0x0000_0002;
 
+/// App Image Flags: Immutable recovery (golden) image, never booted normally but the fallback when the active slot is invalid
+pub const APP_IMAGE_FLAG_RECOVERY: u32 = 0x0000_0004;
+
+/// App Image Flags: Image is encrypted in flash and must be decrypted by the bootloader's crypto driver during the copy to
+/// execution_address. The image CRC is computed over the ciphertext as stored.
+pub const APP_IMAGE_FLAG_ENCRYPTED: u32 = 0x0000_0008;
+
+/// App Image Flags: Mask of every flag bit understood by this DESCRIPTOR_VERSION
+pub const APP_IMAGE_FLAG_ALL: u32 = APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS
+    | APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK
+    | APP_IMAGE_FLAG_RECOVERY
+    | APP_IMAGE_FLAG_ENCRYPTED;
+
+/// Symbolic name of every known app image flag, used for diagnostics
+const APP_IMAGE_FLAG_NAMES: [(u32, &str); 4] = [
+    (APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS, "COPY_TO_EXECUTION_ADDRESS"),
+    (APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK, "SKIP_IMAGE_CRC_CHECK"),
+    (APP_IMAGE_FLAG_RECOVERY, "RECOVERY"),
+    (APP_IMAGE_FLAG_ENCRYPTED, "ENCRYPTED"),
+];
+
+/// Union of every flag in APP_IMAGE_FLAG_NAMES
+const fn named_flags() -> u32 {
+    let mut bits = APP_IMAGE_FLAG_NONE;
+    let mut i = 0;
+    while i < APP_IMAGE_FLAG_NAMES.len() {
+        bits |= APP_IMAGE_FLAG_NAMES[i].0;
+        i += 1;
+    }
+    bits
+}
+
+// Every flag in APP_IMAGE_FLAG_ALL needs a name for diagnostics, and every named flag must be known
+const _: () = assert!(named_flags() == APP_IMAGE_FLAG_ALL);
+
+/// CRC32 engine used for app image integrity checks, static so digests over it can outlive a single call
+static IMAGE_CRC: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+/// Integrity Type: CRC32 (CRC_32_ISO_HDLC) over the image, kept at stored_crc_address
+pub const INTEGRITY_CRC32: u32 = 0;
+
+/// Integrity Type: SHA-256 digest over the image, reserved for a future bootloader and not checked by this crate
+pub const INTEGRITY_SHA256: u32 = 1;
+
+/// Size in bytes of AppImageDescriptor::name
+pub const APP_IMAGE_NAME_SIZE: usize = 16;
+
 /// Size of the DESCRIPTOR_VERSION of the bootable region app image descriptor
 pub const APP_IMAGE_DESCRIPTOR_SIZE: usize = size_of::<AppImageDescriptor>();
 
+/// Size of a region holding a header and one app descriptor, as built by BootableRegionDescriptors::single_slot_region
+pub const SINGLE_SLOT_REGION_SIZE: usize = BOOT_REGION_DESCRIPTOR_SIZE + APP_IMAGE_DESCRIPTOR_SIZE;
+
+// The on-flash format must not change by accident, adding or reordering fields requires updating these deliberately
+const _: () = assert!(BOOT_REGION_DESCRIPTOR_SIZE == 40);
+const _: () = assert!(APP_IMAGE_DESCRIPTOR_SIZE == 68);
+
+// CRC32 checksums are computed over whole u32 words
+const _: () = assert!(BOOT_REGION_DESCRIPTOR_SIZE % size_of::<u32>() == 0);
+const _: () = assert!(APP_IMAGE_DESCRIPTOR_SIZE % size_of::<u32>() == 0);
+
 /// The actual descriptor region header
 #[repr(C, packed)]
 #[derive(Copy, Clone, Debug, Zeroable, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BootableRegionDescriptorHeader {
     /// BOOT_REGION_DESCRIPTOR_SIGNATURE
     pub signature: u32,
@@ -80,6 +241,17 @@ pub struct BootableRegionDescriptorHeader {
     /// Corresponds to which AppImageDescriptor should be booted
     pub active_app_slot: u32,
 
+    /// Bytes reserved after the last AppImageDescriptor for future per-slot metadata. The bootloader never reads them, it
+    /// only needs to skip them when computing where the descriptor region ends.
+    pub reserved_bytes: u32,
+
+    /// Incremented, wrapping from u32::MAX to 0, by the writers in this crate that change header content
+    /// (set_active_slot_in_buffer and so UpdateTransaction::commit, and relocate), so that of two redundant header copies
+    /// the newer one can be told apart, see BootableRegionDescriptors::from_redundant. The CRC repair helpers
+    /// recompute_header_crc and recompute_all_crcs leave it alone, and new and init_region start at 0. Compared as a serial
+    /// number, see is_newer_than.
+    pub sequence: u32,
+
     /// CRC32 checksum of above parameters
     pub header_crc: u32,
 }
@@ -87,6 +259,7 @@ pub struct BootableRegionDescriptorHeader {
 /// The App Image Descriptor for describing layout and usage of corresponding app image
 #[repr(C, packed)]
 #[derive(Copy, Clone, Debug, Zeroable, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AppImageDescriptor {
     /// DESCRIPTOR_VERSION in the format h'MM_mmmm_pp
     pub descriptor_version: u32,
@@ -118,12 +291,157 @@ pub struct AppImageDescriptor {
     /// where to begin execution once the app image is validated and loaded
     pub execution_address: u32,
 
+    /// how many times the bootloader has tried to boot this image without the app confirming a successful boot
+    pub boot_attempts: u32,
+
+    /// short human readable name or tag for the image, UTF-8 padded with zero bytes, see name_str
+    pub name: [u8; APP_IMAGE_NAME_SIZE],
+
+    /// how the image at stored_address is checked, one of the INTEGRITY_* constants
+    pub integrity_type: u32,
+
     /// CRC32 checksum over the above parameters
     pub descriptor_crc: u32,
 }
 
+/// Boot step 5: the memory copy a bootloader performs before executing a copied (non-XIP) app image
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CopyPlan {
+    /// where to copy from, the descriptor's stored_address
+    pub src: u32,
+
+    /// where to copy to, the descriptor's execution_address
+    pub dst: u32,
+
+    /// how many bytes to copy, the descriptor's execution_copy_size_bytes
+    pub len: u32,
+}
+
+/// How a bootloader would load and start an app image, gathered from its descriptor's flags and addresses for logs and
+/// diagnostics
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BootSummary {
+    /// executes in place, APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS is clear
+    pub xip: bool,
+
+    /// where the image is stored, the descriptor's stored_address
+    pub load_from: u32,
+
+    /// where the vector table lives once loaded, see AppImageDescriptor::vector_table_address
+    pub run_at: u32,
+
+    /// how many bytes are copied before executing, 0 for XIP images
+    pub copy_len: u32,
+
+    /// the image CRC is checked before booting, APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK is clear
+    pub crc_checked: bool,
+}
+
+/// An app image descriptor ordered by (security_version, app_version), for picking the best of several slots with max.
+/// Equality follows the same key, so two candidates are equal when both versions match even if other fields differ.
+#[derive(Copy, Clone, Debug)]
+pub struct SlotCandidate(pub AppImageDescriptor);
+
+impl SlotCandidate {
+    /// The (security_version, app_version) pair candidates are ordered by
+    pub const fn key(&self) -> (u32, u32) {
+        (self.0.security_version, self.0.app_version)
+    }
+}
+
+impl PartialEq for SlotCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for SlotCandidate {}
+
+impl PartialOrd for SlotCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SlotCandidate {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Byte order of encoded descriptor fields. Descriptors on flash are always Endianness::ON_FLASH, the others are for
+/// host tooling exchanging descriptors with other formats.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Endianness {
+    /// Least significant byte first, the on-flash format
+    #[default]
+    Little,
+    /// Most significant byte first
+    Big,
+}
+
+impl Endianness {
+    /// Byte order of descriptors on flash, and of from_bytes and write_to
+    pub const ON_FLASH: Endianness = Endianness::Little;
+
+    /// Byte order of the host, the same as as_bytes
+    pub const NATIVE: Endianness = if cfg!(target_endian = "big") {
+        Endianness::Big
+    } else {
+        Endianness::Little
+    };
+
+    /// Check if encoding in this byte order means swapping every field on this host
+    pub(crate) const fn swaps(self) -> bool {
+        match self {
+            Endianness::Little => cfg!(target_endian = "big"),
+            Endianness::Big => cfg!(target_endian = "little"),
+        }
+    }
+}
+
+/// Which of an app image's address ranges an address falls in
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AddressKind {
+    /// \[stored_address, stored_address + image_size_bytes)
+    Stored,
+    /// \[execution_address, execution_address + execution_copy_size_bytes), only for copied images
+    Execution,
+}
+
+/// Which self-reported size in a header disagrees with the compiled layout
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SizeField {
+    /// descriptor_header_size_bytes
+    DescriptorHeaderSize,
+
+    /// app_descriptor_size_bytes
+    AppDescriptorSize,
+}
+
+impl fmt::Display for SizeField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SizeField::DescriptorHeaderSize => write!(f, "descriptor_header_size_bytes"),
+            SizeField::AppDescriptorSize => write!(f, "app_descriptor_size_bytes"),
+        }
+    }
+}
+
 /// Descriptor parsing error conditions
 #[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ParseError {
     /// Descriptor region header does not start with BOOT_REGION_DESCRIPTOR_SIGNATURE
     InvalidSignature,
@@ -138,8 +456,10 @@ pub enum ParseError {
 
     /// App image descriptor CRC32 checksum is invalid or image descriptor is corrupted
     InvalidAppCrc {
-        /// where the app image descriptor was searched for
-        address: *const u32,
+        /// which app slot the descriptor was read from
+        slot: u32,
+        /// where the app image descriptor was searched for, 0 for a descriptor checked in memory with validate
+        address: usize,
         /// what was found at the CRC32 offset in the image descriptor (descriptor_crc parameter)
         found: u32,
         /// what was expected to be computed based on current contents of the image descriptor
@@ -151,132 +471,1216 @@ pub enum ParseError {
 
     /// num_app_slots is 0 or otherwise uninterpretable
     InvalidSlotCount,
+
+    /// Provided buffer is too small to hold the descriptor being parsed
+    BufferTooSmall {
+        /// how many bytes are required
+        needed: usize,
+        /// how many bytes were provided
+        got: usize,
+    },
+
+    /// Active app image security_version is below the minimum allowed, booting it would be a roll-back
+    SecurityVersionRollback {
+        /// security_version of the active app image descriptor
+        found: u32,
+        /// minimum security_version allowed to boot
+        minimum: u32,
+    },
+
+    /// Two app image descriptors describe stored images that overlap each other
+    OverlappingRegions {
+        /// first slot of the overlapping pair
+        slot_a: u32,
+        /// second slot of the overlapping pair
+        slot_b: u32,
+    },
+
+    /// An address plus size in the app image descriptor wraps past the end of the 32-bit address space
+    AddressOverflow {
+        /// which app slot the offending descriptor is in
+        slot: u32,
+    },
+
+    /// App image CRC32 checksum does not match the expected checksum or the image is corrupted
+    InvalidImageCrc {
+        /// what CRC32 checksum was computed over the image contents
+        found: u32,
+        /// what CRC32 checksum the image was expected to have (typically read from stored_crc_address)
+        expected: u32,
+    },
+
+    /// Descriptor was written with a descriptor version this crate can't interpret, see DescriptorVersion::is_compatible_with
+    IncompatibleVersion {
+        /// the full descriptor_version found in the descriptor
+        found: u32,
+        /// the version this crate reads and writes, DESCRIPTOR_VERSION
+        expected: u32,
+    },
+
+    /// A size or offset computed from descriptor fields doesn't fit in the host's address width
+    ArithmeticOverflow,
+
+    /// The active slot holds a recovery image, which is only booted as the fallback for an invalid active slot
+    RecoveryActive {
+        /// the active slot
+        slot: u32,
+    },
+
+    /// Header bytes are all 0xFF or all 0x00: the region was never provisioned, rather than being corrupt
+    ErasedRegion,
+
+    /// App descriptor asks for an image integrity check this build doesn't implement
+    UnsupportedIntegrityType {
+        /// which app slot the descriptor is in
+        slot: u32,
+        /// the integrity_type found in the descriptor
+        integrity_type: u32,
+    },
+
+    /// App descriptor sets APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS but execution_copy_size_bytes doesn't cover the image
+    InconsistentCopySize {
+        /// which app slot is half configured
+        slot: u32,
+    },
+
+    /// App descriptor array starts or extends inside the region header, so the two would be misread as each other
+    DescriptorBaseOverlapsHeader,
+
+    /// An UpdateTransaction step was attempted before the step it depends on, such as commit before a successful verify
+    UpdateOutOfOrder,
+
+    /// An update tried to stage a descriptor into the active slot, which would overwrite the image currently booted
+    ActiveSlotStaged {
+        /// the active slot
+        slot: u32,
+    },
+
+    /// Image CRC at stored_crc_address lies within the stored image it's meant to check
+    CrcAddressInsideImage {
+        /// which app slot is self referencing
+        slot: u32,
+    },
+
+    /// App descriptor describes a zero length image, which would hang the EC if booted
+    EmptyImage {
+        /// which app slot is empty
+        slot: u32,
+    },
+
+    /// App descriptor's descriptor_version differs from the header's
+    VersionSkew {
+        /// which app slot differs
+        slot: u32,
+        /// descriptor_version reported by the app descriptor
+        found: u32,
+        /// descriptor_version reported by the header
+        expected: u32,
+    },
+
+    /// A FlashRead reader failed to read descriptor bytes
+    FlashReadFailed {
+        /// address the failed read started at
+        address: u32,
+    },
+
+    /// A FlashWrite writer failed to write descriptor bytes
+    FlashWriteFailed {
+        /// address the failed write started at
+        address: u32,
+    },
+
+    /// Stored app image extends outside the flash partition the integrator declared for it
+    ImageExceedsPartition {
+        /// which app slot doesn't fit
+        slot: u32,
+    },
+
+    /// Header claims more app slots than the caller allows
+    TooManySlots {
+        /// num_app_slots reported by the header
+        found: u32,
+        /// maximum number of slots allowed
+        max: u32,
+    },
+
+    /// Header reports a descriptor size that doesn't match this DESCRIPTOR_VERSION's layout, so field offsets would differ
+    SizeMismatch {
+        /// which size field disagrees
+        field: SizeField,
+        /// size reported by the header
+        found: u32,
+        /// size compiled into this crate
+        expected: u32,
+    },
+
+    /// App descriptor at one index of the descriptor array declares a different app_slot_number, only reported by strict parsing
+    SlotNumberMismatch {
+        /// index of the descriptor in the app descriptor array
+        index: u32,
+        /// app_slot_number the descriptor declares
+        declared: u32,
+    },
+
+    /// App descriptor sets flag bits not understood by this DESCRIPTOR_VERSION, only reported by strict parsing
+    UnknownFlags {
+        /// which app slot set the unknown flags
+        slot: u32,
+        /// the unknown flag bits
+        bits: u32,
+    },
+
+    /// Copying the app image to its execution address would overwrite memory the bootloader must protect
+    ExecutionRegionConflict {
+        /// which app slot would perform the conflicting copy
+        slot: u32,
+    },
+}
+
+// derive(defmt::Format) takes references to fields, which isn't allowed for packed structs, so copy fields out instead
+#[cfg(feature = "defmt")]
+impl defmt::Format for BootableRegionDescriptorHeader {
+    fn format(&self, fmt: defmt::Formatter) {
+        let Self {
+            signature,
+            descriptor_version,
+            descriptor_header_size_bytes,
+            app_descriptor_size_bytes,
+            app_descriptor_base_address,
+            num_app_slots,
+            active_app_slot,
+            reserved_bytes,
+            sequence,
+            header_crc,
+        } = *self;
+
+        defmt::write!(
+            fmt,
+            "BootableRegionDescriptorHeader {{ signature: {=u32:#x}, descriptor_version: {=u32:#x}, descriptor_header_size_bytes: {=u32}, app_descriptor_size_bytes: {=u32}, app_descriptor_base_address: {=u32:#x}, num_app_slots: {=u32}, active_app_slot: {=u32}, reserved_bytes: {=u32}, sequence: {=u32}, header_crc: {=u32:#x} }}",
+            signature,
+            descriptor_version,
+            descriptor_header_size_bytes,
+            app_descriptor_size_bytes,
+            app_descriptor_base_address,
+            num_app_slots,
+            active_app_slot,
+            reserved_bytes,
+            sequence,
+            header_crc,
+        );
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for AppImageDescriptor {
+    fn format(&self, fmt: defmt::Formatter) {
+        let Self {
+            descriptor_version,
+            app_slot_number,
+            app_version,
+            security_version,
+            flags,
+            stored_address,
+            image_size_bytes,
+            stored_crc_address,
+            execution_copy_size_bytes,
+            execution_address,
+            boot_attempts,
+            name: _,
+            integrity_type,
+            descriptor_crc,
+        } = *self;
+
+        defmt::write!(
+            fmt,
+            "AppImageDescriptor {{ descriptor_version: {=u32:#x}, app_slot_number: {=u32}, app_version: {=u32:#x}, security_version: {=u32}, flags: {=u32:#x}, stored_address: {=u32:#x}, image_size_bytes: {=u32}, stored_crc_address: {=u32:#x}, execution_copy_size_bytes: {=u32}, execution_address: {=u32:#x}, boot_attempts: {=u32}, name: {=str}, integrity_type: {=u32}, descriptor_crc: {=u32:#x} }}",
+            descriptor_version,
+            app_slot_number,
+            app_version,
+            security_version,
+            flags,
+            stored_address,
+            image_size_bytes,
+            stored_crc_address,
+            execution_copy_size_bytes,
+            execution_address,
+            boot_attempts,
+            self.name_str(),
+            integrity_type,
+            descriptor_crc,
+        );
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::InvalidSignature => write!(f, "invalid descriptor header signature"),
+            ParseError::InvalidHeaderCrc { found, expected } => {
+                write!(f, "invalid header CRC: found {found:#010x}, expected {expected:#010x}")
+            }
+            ParseError::InvalidAppCrc {
+                slot,
+                address,
+                found,
+                expected,
+            } => write!(
+                f,
+                "invalid app descriptor CRC for slot {slot} at {address:#x}: found {found:#010x}, expected {expected:#010x}"
+            ),
+            ParseError::InvalidAppSlot => write!(f, "app slot out of range"),
+            ParseError::InvalidSlotCount => write!(f, "invalid app slot count"),
+            ParseError::BufferTooSmall { needed, got } => {
+                write!(f, "buffer too small: needed {needed} bytes, got {got}")
+            }
+            ParseError::SecurityVersionRollback { found, minimum } => {
+                write!(f, "security version rollback: found {found}, minimum {minimum}")
+            }
+            ParseError::OverlappingRegions { slot_a, slot_b } => {
+                write!(f, "stored images for slots {slot_a} and {slot_b} overlap")
+            }
+            ParseError::AddressOverflow { slot } => write!(f, "address range overflows in slot {slot}"),
+            ParseError::InvalidImageCrc { found, expected } => {
+                write!(f, "invalid image CRC: found {found:#010x}, expected {expected:#010x}")
+            }
+            ParseError::IncompatibleVersion { found, expected } => {
+                write!(
+                    f,
+                    "incompatible descriptor version: found {found:#010x}, expected one compatible with {expected:#010x}"
+                )
+            }
+            ParseError::ArithmeticOverflow => write!(f, "descriptor size or offset overflows"),
+            ParseError::RecoveryActive { slot } => write!(f, "active slot {slot} holds a recovery image"),
+            ParseError::ErasedRegion => write!(f, "descriptor region is erased"),
+            ParseError::UnsupportedIntegrityType { slot, integrity_type } => {
+                write!(f, "unsupported integrity type {integrity_type} in slot {slot}")
+            }
+            ParseError::InconsistentCopySize { slot } => {
+                write!(f, "copy size of slot {slot} doesn't match its image size")
+            }
+            ParseError::DescriptorBaseOverlapsHeader => write!(f, "app descriptors overlap the region header"),
+            ParseError::UpdateOutOfOrder => write!(f, "update step out of order"),
+            ParseError::ActiveSlotStaged { slot } => write!(f, "cannot stage an update into active slot {slot}"),
+            ParseError::CrcAddressInsideImage { slot } => write!(f, "image CRC of slot {slot} lies inside the image"),
+            ParseError::EmptyImage { slot } => write!(f, "empty image in slot {slot}"),
+            ParseError::VersionSkew { slot, found, expected } => write!(
+                f,
+                "descriptor version skew in slot {slot}: found {found:#010x}, expected {expected:#010x}"
+            ),
+            ParseError::FlashReadFailed { address } => write!(f, "flash read failed at {address:#x}"),
+            ParseError::FlashWriteFailed { address } => write!(f, "flash write failed at {address:#x}"),
+            ParseError::ImageExceedsPartition { slot } => write!(f, "image in slot {slot} exceeds its partition"),
+            ParseError::TooManySlots { found, max } => write!(f, "too many app slots: found {found}, max {max}"),
+            ParseError::SizeMismatch { field, found, expected } => {
+                write!(f, "{field} mismatch: found {found}, expected {expected}")
+            }
+            ParseError::SlotNumberMismatch { index, declared } => {
+                write!(f, "app descriptor at index {index} declares slot {declared}")
+            }
+            ParseError::UnknownFlags { slot, bits } => write!(f, "unknown flags {bits:#010x} set in slot {slot}"),
+            ParseError::ExecutionRegionConflict { slot } => {
+                write!(f, "execution region of slot {slot} overlaps a reserved region")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Report a ParseError as InvalidData with its Display message, for host tools built around std::io
+#[cfg(feature = "std")]
+impl From<ParseError> for std::io::Error {
+    fn from(err: ParseError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, std::format!("{err}"))
+    }
+}
+
+/// Where BootableRegionDescriptors reads app image descriptors from
+#[derive(Copy, Clone)]
+enum AppDescriptorSource<'a> {
+    /// readable location of AppImageDescriptor\[num_app_slots\], which may differ from app_descriptor_base_address
+    Memory(*const u32, PhantomData<&'a [u8]>),
+
+    /// flash reader holding AppImageDescriptor\[num_app_slots\] at app_descriptor_base_address
+    Reader(&'a dyn ErasedFlashRead),
 }
 
 /// Manager struct to make loading and writing bootable region header and app image descriptors easier
-pub struct BootableRegionDescriptors {
-    _base_address: *const u8,
+pub struct BootableRegionDescriptors<'a> {
+    /// ties descriptors parsed from a buffer or reader to that buffer or reader's lifetime
+    app_descriptors: AppDescriptorSource<'a>,
     header: BootableRegionDescriptorHeader,
+    /// copy of the active app descriptor taken when it was validated at construction
+    active: AppImageDescriptor,
 }
 
-impl BootableRegionDescriptors {
-    /// Attempt to load from address the bootable region descriptors header and app images
-    pub fn from_address(address: *const u32) -> Result<BootableRegionDescriptors, ParseError> {
+impl<'a> BootableRegionDescriptors<'a> {
+    /// Validate every app descriptor described by header through app_descriptors, keeping a copy of the active one
+    fn validated(
+        app_descriptors: AppDescriptorSource<'a>,
+        header: BootableRegionDescriptorHeader,
+    ) -> Result<Self, ParseError> {
         // cache off basic data used later
-        let this = Self {
-            _base_address: address as *const u8,
-            header: BootableRegionDescriptorHeader::from_address(address)?,
+        let mut this = Self {
+            app_descriptors,
+            header,
+            active: AppImageDescriptor::default(),
         };
 
         // loop over and validate all app slot descriptors, pass up failures if they exist
         for i in 0..this.header.num_app_slots {
-            let _app_image_descriptor =
-                AppImageDescriptor::from_region(this.header.app_descriptor_base_address as *const u32, i)?;
+            let app_image_descriptor = this.read_app(i)?;
+            if i == this.header.active_app_slot {
+                this.active = app_image_descriptor;
+            }
         }
 
-        // only allow construction of bootable region descriptors from memory if all slots are valid
+        // only allow construction of bootable region descriptors if all slots are valid
         Ok(this)
     }
 
-    /// Attempt to load from the provided buffer the bootable region descriptors header and app images.
-    /// # Safety: This function uses pointer arithmetic so the provided buffer MUST encompass both the BL header and all app image descriptors.
-    pub unsafe fn from_region(
-        buffer: *const u32,
-        original_address: u32,
-    ) -> Result<BootableRegionDescriptors, ParseError> {
-        // cache off basic data used later
-        let this = Self {
-            _base_address: buffer as *const u8,
-            header: BootableRegionDescriptorHeader::from_address(buffer)?,
-        };
+    /// Attempt to load from address the bootable region descriptors header and app images. address must be 4 byte aligned,
+    /// which is checked in debug builds. Headers claiming more than MAX_REASONABLE_SLOTS app slots are rejected, use
+    /// from_address_bounded for larger regions.
+    pub fn from_address(address: *const u32) -> Result<Self, ParseError> {
+        let header = BootableRegionDescriptorHeader::from_address(address)?;
+        header.validate_slot_count(MAX_REASONABLE_SLOTS)?;
+        header.validate_descriptor_base(address as usize)?;
 
-        // calculate the app descriptor offset in the buffer based on the difference from the original address
-        let adjusted_app_descriptor_base_address =
-            (buffer as u32) + (this.header.app_descriptor_base_address - original_address);
-        // loop over and validate all app slot descriptors, pass up failures if they exist
-        for i in 0..this.header.num_app_slots {
-            let _app_image_descriptor =
-                AppImageDescriptor::from_region(adjusted_app_descriptor_base_address as *const u32, i)?;
+        Self::validated(
+            AppDescriptorSource::Memory(header.app_descriptor_base_address as *const u32, PhantomData),
+            header,
+        )
+    }
+
+    /// Like from_address, but for a descriptor region known to span region_len bytes from address, such as one flash
+    /// sector. Before any app descriptor is read, the whole region as described by the header, and the app descriptor
+    /// array at app_descriptor_base_address, are checked to lie within those bytes.
+    pub fn from_address_bounded(address: *const u32, region_len: usize) -> Result<Self, ParseError> {
+        if region_len < BOOT_REGION_DESCRIPTOR_SIZE {
+            return Err(ParseError::BufferTooSmall {
+                needed: BOOT_REGION_DESCRIPTOR_SIZE,
+                got: region_len,
+            });
+        }
+        let header = BootableRegionDescriptorHeader::from_address(address)?;
+        header.validate_descriptor_base(address as usize)?;
+
+        let region_size = header.region_size()?;
+        if region_len < region_size {
+            return Err(ParseError::BufferTooSmall {
+                needed: region_size,
+                got: region_len,
+            });
+        }
+
+        let needed = (header.app_descriptor_base_address as usize)
+            .checked_sub(address as usize)
+            .and_then(|offset| {
+                (header.num_app_slots as usize)
+                    .checked_mul(APP_IMAGE_DESCRIPTOR_SIZE)?
+                    .checked_add(offset)
+            })
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        if region_len < needed {
+            return Err(ParseError::BufferTooSmall {
+                needed,
+                got: region_len,
+            });
         }
 
-        // only allow construction of bootable region descriptors from memory if all slots are valid
+        Self::validated(
+            AppDescriptorSource::Memory(header.app_descriptor_base_address as *const u32, PhantomData),
+            header,
+        )
+    }
+
+    /// Like from_address, but additionally apply the checks in validate_strict
+    pub fn from_address_strict(address: *const u32) -> Result<Self, ParseError> {
+        let this = Self::from_address(address)?;
+        this.validate_strict()?;
         Ok(this)
     }
 
+    /// Attempt to load from the provided buffer the bootable region descriptors header and app images. The header and the
+    /// app descriptors within buffer must be 4 byte aligned, misalignment is caught by a debug assertion.
+    ///
+    /// # Safety
+    /// This function uses pointer arithmetic so the provided buffer MUST encompass both the BL header and all app image descriptors.
+    pub unsafe fn from_region(buffer: *const u32, original_address: u32) -> Result<Self, ParseError> {
+        let header = BootableRegionDescriptorHeader::from_address(buffer)?;
+        header.validate_slot_count(MAX_REASONABLE_SLOTS)?;
+        header.validate_descriptor_base(original_address as usize)?;
+
+        // calculate the app descriptor offset in the buffer based on the difference from the original address, which is
+        // negative if the app descriptors precede the header
+        let offset = isize::try_from(header.app_descriptor_base_address as i64 - original_address as i64)
+            .map_err(|_| ParseError::ArithmeticOverflow)?;
+        let app_descriptors = (buffer as *const u8).offset(offset) as *const u32;
+        debug_assert!(
+            app_descriptors.is_aligned(),
+            "app descriptors address {app_descriptors:p} is not 4 byte aligned"
+        );
+
+        Self::validated(AppDescriptorSource::Memory(app_descriptors, PhantomData), header)
+    }
+
     /// Attempt to load the bootable region descriptors from the provided buffers for header and app images.
-    /// This is useful for cases where the header and app descriptors are stored in different memory regions.
+    /// This is useful for cases where the header and app descriptors are stored in different memory regions. Both buffers
+    /// must be 4 byte aligned, misaligned buffers are caught by a debug assertion.
     pub fn from_header_and_app_regions(
         header_buffer: *const u32,
         app_descriptors_buffer: *const u32,
-    ) -> Result<BootableRegionDescriptors, ParseError> {
-        // cache off basic data used later
-        let this = Self {
-            _base_address: header_buffer as *const u8,
-            header: BootableRegionDescriptorHeader::from_address(header_buffer)?,
-        };
+    ) -> Result<Self, ParseError> {
+        debug_assert!(
+            app_descriptors_buffer.is_aligned(),
+            "app descriptors address {app_descriptors_buffer:p} is not 4 byte aligned"
+        );
+        let header = BootableRegionDescriptorHeader::from_address(header_buffer)?;
+        header.validate_slot_count(MAX_REASONABLE_SLOTS)?;
 
-        // loop over and validate all app slot descriptors, pass up failures if they exist
-        for i in 0..this.header.num_app_slots {
-            let _app_image_descriptor = AppImageDescriptor::from_region(app_descriptors_buffer, i)?;
-        }
+        Self::validated(AppDescriptorSource::Memory(app_descriptors_buffer, PhantomData), header)
+    }
 
-        // only allow construction of bootable region descriptors from memory if all slots are valid
-        Ok(this)
+    /// Attempt to load the bootable region descriptors from a region read into a buffer, with the header at the start of region.
+    ///
+    /// In this path app_descriptor_base_address is treated as a byte offset of the app descriptors within region, not as an
+    /// absolute memory address, so nothing outside region is ever read.
+    pub fn from_bytes(region: &'a [u8]) -> Result<Self, ParseError> {
+        Self::from_bytes_at(region, 0)
     }
 
-    /// Once a valid descriptor set is read, request the currently active marked App Image Descriptor
-    pub fn get_active_slot(&self) -> AppImageDescriptor {
-        // can't fail as BootableRegionDescriptors only constructs if all app descriptors are valid
-        AppImageDescriptor::from_region(
-            self.header.app_descriptor_base_address as *const u32,
-            self.header.active_app_slot,
+    /// Like from_bytes, but with the header at header_offset within region rather than at the start. This covers layouts
+    /// that place the app descriptors before the header, such as a header at the end of the sector. app_descriptor_base_address
+    /// is still a byte offset from the start of region.
+    pub fn from_bytes_at(region: &'a [u8], header_offset: usize) -> Result<Self, ParseError> {
+        let header_bytes = region.get(header_offset..).ok_or(ParseError::BufferTooSmall {
+            needed: header_offset.saturating_add(BOOT_REGION_DESCRIPTOR_SIZE),
+            got: region.len(),
+        })?;
+        let header = BootableRegionDescriptorHeader::from_bytes(header_bytes)?;
+        header.validate_descriptor_base(header_offset)?;
+
+        // make sure every app descriptor lies within region before reading any of them
+        let offset = header.app_descriptor_base_address as usize;
+        let needed = (header.num_app_slots as usize)
+            .checked_mul(APP_IMAGE_DESCRIPTOR_SIZE)
+            .and_then(|size| size.checked_add(offset))
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        if region.len() < needed {
+            return Err(ParseError::BufferTooSmall {
+                needed,
+                got: region.len(),
+            });
+        }
+
+        Self::validated(
+            AppDescriptorSource::Memory(region[offset..].as_ptr() as *const u32, PhantomData),
+            header,
         )
-        .unwrap()
     }
 
-    pub fn get_app_descriptor_base_address(&self) -> u32 {
-        self.header.app_descriptor_base_address
-    }
+    /// Attempt to load the bootable region descriptors through reader instead of dereferencing memory, with the header at
+    /// header_addr and the app descriptors at app_descriptor_base_address. Every later app descriptor access also goes
+    /// through reader, which is borrowed for the lifetime of the result.
+    pub fn from_reader<R: FlashRead>(reader: &'a R, header_addr: u32) -> Result<Self, ParseError> {
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        reader.read_erased(header_addr, &mut buf)?;
+        let header = BootableRegionDescriptorHeader::from_bytes(&buf)?;
+        header.validate_slot_count(MAX_REASONABLE_SLOTS)?;
+        header.validate_descriptor_base(header_addr as usize)?;
 
-    pub fn get_active_slot_number(&self) -> u32 {
-        self.header.active_app_slot
+        Self::validated(AppDescriptorSource::Reader(reader), header)
     }
 
-    /// Get descriptor for a specific app slot
-    pub fn get_app_at_slot(&self, app_slot: u32) -> Result<AppImageDescriptor, ParseError> {
-        if app_slot >= self.header.num_app_slots {
-            return Err(ParseError::InvalidAppSlot);
+    /// Load from whichever of two redundant copies of the region is valid, each laid out as for from_bytes. If both are
+    /// valid the one whose header is newer by sequence wins, the primary on a tie, see is_newer_than. Writing the copy not in
+    /// use with a higher sequence, and only then the other, means a power loss during either write leaves one valid copy to
+    /// boot from.
+    /// If neither copy is valid, the primary's error is returned.
+    pub fn from_redundant(primary: &'a [u8], backup: &'a [u8]) -> Result<Self, ParseError> {
+        match (Self::from_bytes(primary), Self::from_bytes(backup)) {
+            (Ok(primary), Ok(backup)) => {
+                if backup.header.is_newer_than(&primary.header) {
+                    Ok(backup)
+                } else {
+                    Ok(primary)
+                }
+            }
+            (Ok(primary), Err(_)) => Ok(primary),
+            (Err(_), Ok(backup)) => Ok(backup),
+            (Err(err), Err(_)) => Err(err),
         }
-
-        // can't fail as BootableRegionDescriptors only constructs if all app descriptors are valid
-        AppImageDescriptor::from_region(self.header.app_descriptor_base_address as *const u32, app_slot)
     }
-}
 
-impl BootableRegionDescriptorHeader {
-    /// Attempt to load a bootable region descriptor header from provided address
-    pub fn from_address(address: *const u32) -> Result<BootableRegionDescriptorHeader, ParseError> {
-        let unvalidated = unsafe { *(address as *const BootableRegionDescriptorHeader) };
+    /// Build the simplest valid region for factory provisioning: a header with one app slot, active, followed immediately
+    /// by app, with every CRC computed. app is placed in slot 0, overriding its app_slot_number. descriptor_base is where
+    /// the descriptor will sit once flashed, the address the region is linked at plus BOOT_REGION_DESCRIPTOR_SIZE.
+    pub const fn single_slot_region(app: AppImageDescriptor, descriptor_base: u32) -> [u8; SINGLE_SLOT_REGION_SIZE] {
+        let mut app = app;
+        app.app_slot_number = 0;
+        app.descriptor_crc = app.compute_crc();
 
-        if unvalidated.signature != BOOT_REGION_DESCRIPTOR_SIGNATURE {
-            Err(ParseError::InvalidSignature)
-        } else if !unvalidated.is_crc_valid() {
-            Err(ParseError::InvalidHeaderCrc {
-                found: unvalidated.header_crc,
-                expected: unvalidated.compute_crc(),
-            })
-        } else if unvalidated.num_app_slots < 1 {
-            Err(ParseError::InvalidSlotCount)
-        } else if unvalidated.active_app_slot >= unvalidated.num_app_slots {
-            Err(ParseError::InvalidAppSlot)
-        } else {
-            Ok(unvalidated)
-        }
+        build_region(BootableRegionDescriptorHeader::new(1, 0, descriptor_base), &[app])
     }
 
-    /// Generate at compile time a descriptor region header. Useful for initialization and explicit linker placement for debug scenarios
-    pub const fn new(
-        app_slot_count: u32,
+    /// Read and validate the app descriptors described by header one at a time through reader, for streaming validation
+    /// on parts without the RAM to buffer the whole region. Only the descriptor being yielded is held in memory, and a bad
+    /// slot is reported without ending the iteration.
+    pub fn slot_reader<'r, R: FlashRead>(
+        reader: &'r R,
+        header: &BootableRegionDescriptorHeader,
+    ) -> impl Iterator<Item = Result<AppImageDescriptor, ParseError>> + 'r {
+        let base_address = header.app_descriptor_base_address;
+        (0..header.num_app_slots).map(move |slot| reader::read_app(reader, base_address, slot))
+    }
+
+    /// Diagnose a region laid out as for from_bytes, collecting every error found instead of stopping at the first. Each error
+    /// is stored in errors in the order found, and the total count is returned, which may exceed errors.len(). A header with
+    /// only a bad header_crc is still used to locate the app descriptors, any other header error ends the scan. The boot
+    /// path should keep using the fail fast constructors.
+    pub fn validate_all(region: &[u8], errors: &mut [ParseError]) -> usize {
+        let mut found = 0;
+        let mut report = |err: ParseError| {
+            if let Some(slot) = errors.get_mut(found) {
+                *slot = err;
+            }
+            found += 1;
+        };
+
+        let header = match BootableRegionDescriptorHeader::from_bytes(region) {
+            Ok(header) => header,
+            Err(err @ ParseError::InvalidHeaderCrc { .. }) => {
+                report(err);
+                // can't fail as from_bytes got as far as checking the CRC
+                BootableRegionDescriptorHeader::read_unchecked(region).unwrap()
+            }
+            Err(err) => {
+                report(err);
+                return found;
+            }
+        };
+        if let Err(err) = header.validate_descriptor_base(0) {
+            report(err);
+        }
+
+        for slot in 0..header.num_app_slots {
+            let Some((offset, end)) = (slot as usize)
+                .checked_mul(APP_IMAGE_DESCRIPTOR_SIZE)
+                .and_then(|offset| offset.checked_add(header.app_descriptor_base_address as usize))
+                .and_then(|offset| Some((offset, offset.checked_add(APP_IMAGE_DESCRIPTOR_SIZE)?)))
+            else {
+                report(ParseError::ArithmeticOverflow);
+                break;
+            };
+            let Some(bytes) = region.get(offset..end) else {
+                // every later slot is out of range too
+                report(ParseError::BufferTooSmall {
+                    needed: end,
+                    got: region.len(),
+                });
+                break;
+            };
+
+            if let Err(err) = AppImageDescriptor::from_encoded_bytes(bytes, cfg!(target_endian = "big"), Some(slot)) {
+                report(err);
+            }
+        }
+
+        found
+    }
+
+    /// Stamp a fresh, empty region into an erased or zeroed buffer for provisioning: a valid header at offset 0 with
+    /// num_slots app slots starting at region offset descriptor_base, slot 0 active and sequence 0, and every slot a blank
+    /// (all zero, valid descriptor_crc) descriptor. The result parses with from_bytes. Nothing is written unless every slot fits in
+    /// region, and bytes outside the header and descriptors are left as they are.
+    pub fn init_region(region: &mut [u8], num_slots: u32, descriptor_base: u32) -> Result<(), ParseError> {
+        if num_slots == 0 {
+            return Err(ParseError::InvalidSlotCount);
+        }
+
+        let header = BootableRegionDescriptorHeader::new(num_slots, 0, descriptor_base);
+        header.validate_descriptor_base(0)?;
+        let needed = header
+            .descriptor_offset(num_slots - 1)?
+            .checked_add(APP_IMAGE_DESCRIPTOR_SIZE)
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        if region.len() < needed {
+            return Err(ParseError::BufferTooSmall {
+                needed,
+                got: region.len(),
+            });
+        }
+
+        header.write_to(region)?;
+        for slot in 0..num_slots {
+            AppImageDescriptor::default().write_to_region(region, &header, slot)?;
+        }
+
+        Ok(())
+    }
+
+    /// Persistently change the active slot of the region whose header starts at the start of region, recomputing
+    /// header_crc, so the header bytes can be written back to flash. This is how a bootloader makes a fall back to
+    /// recovery stick across resets, where the header's set_active_slot only changes a copy in RAM. sequence is incremented.
+    /// The header must be valid beforehand, and only its bytes are changed, so region need not hold the app descriptors.
+    pub fn set_active_slot_in_buffer(region: &mut [u8], slot: u32) -> Result<(), ParseError> {
+        let mut header = BootableRegionDescriptorHeader::from_bytes(region)?;
+        header.set_active_slot(slot)?;
+        header.bump_sequence();
+        header.write_to(region)?;
+
+        Ok(())
+    }
+
+    /// Move a region, laid out with absolute addresses as for from_address, from being linked at old_base to new_base.
+    /// app_descriptor_base_address keeps its offset from the start of the region, and every CRC in region is recomputed.
+    /// App images don't move, so stored_address, stored_crc_address and execution_address are left as they are, as are any
+    /// reserved_bytes after the app descriptors. sequence is incremented. The app descriptors must lie within region, at or
+    /// after old_base.
+    pub fn relocate(region: &mut [u8], old_base: u32, new_base: u32) -> Result<(), ParseError> {
+        rewrite_region(region, |header| {
+            let offset = header
+                .app_descriptor_base_address
+                .checked_sub(old_base)
+                .ok_or(ParseError::ArithmeticOverflow)?;
+            header.app_descriptor_base_address = new_base.checked_add(offset).ok_or(ParseError::ArithmeticOverflow)?;
+            header.bump_sequence();
+            Ok(offset as usize)
+        })
+    }
+
+    /// Like from_bytes, but reject headers claiming more than max_slots app descriptors before reading any of them, so a
+    /// corrupted or malicious num_app_slots can't drive an unbounded scan. Every descriptor is also bounds checked against
+    /// region, as in from_bytes.
+    pub fn from_bytes_bounded(region: &'a [u8], max_slots: u32) -> Result<Self, ParseError> {
+        BootableRegionDescriptorHeader::from_bytes(region)?.validate_slot_count(max_slots)?;
+
+        Self::from_bytes(region)
+    }
+
+    /// Like from_bytes, but additionally apply the checks in validate_strict
+    pub fn from_bytes_strict(region: &'a [u8]) -> Result<Self, ParseError> {
+        let this = Self::from_bytes(region)?;
+        this.validate_strict()?;
+        Ok(this)
+    }
+
+    /// Once a valid descriptor set is read, request the currently active marked App Image Descriptor. This is the copy
+    /// validated at construction, so it never fails, even through a flash reader. See active_slot_rechecked to re-read it.
+    pub fn get_active_slot(&self) -> AppImageDescriptor {
+        self.active
+    }
+
+    /// Re-read and re-validate the active App Image Descriptor from flash instead of trusting the validation done at
+    /// construction. Call right before jumping to catch a descriptor that has been corrupted since the region was loaded.
+    pub fn active_slot_rechecked(&self) -> Result<AppImageDescriptor, ParseError> {
+        self.read_app(self.header.active_app_slot)
+    }
+
+    pub fn get_app_descriptor_base_address(&self) -> u32 {
+        self.header.app_descriptor_base_address
+    }
+
+    pub fn get_active_slot_number(&self) -> u32 {
+        self.header.active_app_slot
+    }
+
+    /// The number of app slots in the region
+    pub fn num_slots(&self) -> u32 {
+        self.header.num_app_slots
+    }
+
+    /// The index of the active app slot
+    pub fn active_slot_index(&self) -> u32 {
+        self.header.active_app_slot
+    }
+
+    /// The validated header this region was loaded with
+    pub fn header(&self) -> &BootableRegionDescriptorHeader {
+        &self.header
+    }
+
+    /// Get descriptor for a specific app slot
+    pub fn get_app_at_slot(&self, app_slot: u32) -> Result<AppImageDescriptor, ParseError> {
+        if app_slot >= self.header.num_app_slots {
+            return Err(ParseError::InvalidAppSlot);
+        }
+
+        // can't fail as BootableRegionDescriptors only constructs if all app descriptors are valid
+        self.read_app(app_slot)
+    }
+
+    /// Read and validate app_slot from wherever this region's app descriptors live
+    fn read_app(&self, app_slot: u32) -> Result<AppImageDescriptor, ParseError> {
+        match self.app_descriptors {
+            AppDescriptorSource::Memory(app_descriptors, _) => {
+                AppImageDescriptor::from_region_bytes(app_descriptors as *const u8, app_slot)
+            }
+            AppDescriptorSource::Reader(reader) => {
+                reader::read_app(reader, self.header.app_descriptor_base_address, app_slot)
+            }
+        }
+    }
+
+    /// Every slot index other than the active one, in ascending order. These are where an update can be staged.
+    pub fn inactive_slots(&self) -> impl Iterator<Item = u32> {
+        let active = self.header.active_app_slot;
+        (0..self.header.num_app_slots).filter(move |&slot| slot != active)
+    }
+
+    /// The lowest inactive slot index, the "other" slot in an A/B layout. None for a single slot region.
+    pub fn first_inactive_slot(&self) -> Option<u32> {
+        self.inactive_slots().next()
+    }
+
+    /// Request the first app image descriptor flagged APP_IMAGE_FLAG_RECOVERY, if any. Slots that no longer validate are
+    /// skipped rather than ending the search.
+    pub fn recovery_slot(&self) -> Option<AppImageDescriptor> {
+        (0..self.header.num_app_slots)
+            .filter_map(|slot| self.get_app_at_slot(slot).ok())
+            .find(AppImageDescriptor::is_recovery)
+    }
+
+    /// Request the active app image descriptor, re-reading and re-validating it since construction, falling back to the
+    /// recovery slot if the active descriptor no longer validates (e.g. after an interrupted update). An active slot
+    /// flagged APP_IMAGE_FLAG_RECOVERY is never booted normally, so it also falls back, failing with RecoveryActive if no
+    /// recovery slot validates, as does a blank active slot, failing with EmptyImage. Otherwise returns the active slot's
+    /// error if there is no valid recovery slot either.
+    pub fn boot_or_recovery(&self) -> Result<AppImageDescriptor, ParseError> {
+        let slot = self.header.active_app_slot;
+        self.get_app_at_slot(slot)
+            .and_then(|app| {
+                if app.is_recovery() {
+                    Err(ParseError::RecoveryActive { slot })
+                } else if app.is_blank() {
+                    Err(ParseError::EmptyImage { slot })
+                } else {
+                    Ok(app)
+                }
+            })
+            .or_else(|err| self.recovery_slot().ok_or(err))
+    }
+
+    /// Request the active App Image Descriptor, only if its security_version is at least min_security_version.
+    /// min_security_version would typically come from a monotonic counter held in fuses or OTP. Fails with RecoveryActive
+    /// if the active slot is flagged APP_IMAGE_FLAG_RECOVERY, as recovery images are never booted normally, and with
+    /// EmptyImage if the active slot is blank, as in a region fresh from init_region.
+    pub fn select_boot_slot(&self, min_security_version: u32) -> Result<AppImageDescriptor, ParseError> {
+        let active = self.get_active_slot();
+        let slot = self.header.active_app_slot;
+
+        if active.is_recovery() {
+            Err(ParseError::RecoveryActive { slot })
+        } else if active.is_blank() {
+            Err(ParseError::EmptyImage { slot })
+        } else if active.security_version < min_security_version {
+            Err(ParseError::SecurityVersionRollback {
+                found: active.security_version,
+                minimum: min_security_version,
+            })
+        } else {
+            Ok(active)
+        }
+    }
+
+    /// Map addr, such as a fault address from a failed image copy, to the lowest slot whose stored or execution range
+    /// contains it. A slot's stored range is checked before its execution range. Slots that no longer validate are skipped.
+    pub fn slot_for_address(&self, addr: u32) -> Option<(u32, AddressKind)> {
+        let contains = |start: u32, end: Option<u32>| end.is_some_and(|end| start <= addr && addr < end);
+
+        (0..).zip(self.iter()).find_map(|(slot, app)| {
+            let app = app.ok()?;
+            if contains(app.stored_address, app.stored_end()) {
+                Some((slot, AddressKind::Stored))
+            } else if app.is_copy_to_execution() && contains(app.execution_address, app.execution_end()) {
+                Some((slot, AddressKind::Execution))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Check that no two app image descriptors describe overlapping stored image ranges
+    pub fn check_region_overlaps(&self) -> Result<(), ParseError> {
+        let stored_range = |slot: u32| -> Result<(u32, u32), ParseError> {
+            let app = self.get_app_at_slot(slot)?;
+            let end = app.stored_end().ok_or(ParseError::AddressOverflow { slot })?;
+            Ok((app.stored_address, end))
+        };
+
+        for slot_a in 0..self.header.num_app_slots {
+            let (start_a, end_a) = stored_range(slot_a)?;
+
+            for slot_b in (slot_a + 1)..self.header.num_app_slots {
+                let (start_b, end_b) = stored_range(slot_b)?;
+
+                // half open ranges [start, end) intersect, empty images never overlap anything
+                if start_a < end_b && start_b < end_a {
+                    return Err(ParseError::OverlappingRegions { slot_a, slot_b });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check if candidate_slot holds a strictly newer app_version than the active slot, so an update handler can reject a
+    /// stale payload before committing it
+    pub fn is_update_newer(&self, candidate_slot: u32) -> Result<bool, ParseError> {
+        let candidate = self.get_app_at_slot(candidate_slot)?;
+        let active = self.get_app_at_slot(self.header.active_app_slot)?;
+        Ok(candidate.app_version > active.app_version)
+    }
+
+    /// Checks left out of the default parsers for backward and forward compatibility: check_slot_numbers, then
+    /// check_unknown_flags
+    pub fn validate_strict(&self) -> Result<(), ParseError> {
+        self.check_slot_numbers()?;
+        self.check_unknown_flags()
+    }
+
+    /// Check that the descriptor at each index declares that index as its app_slot_number. Reports the lowest offending index.
+    pub fn check_slot_numbers(&self) -> Result<(), ParseError> {
+        for (index, app) in (0..).zip(self.iter()) {
+            let app = app?;
+            if app.app_slot_number != index {
+                return Err(ParseError::SlotNumberMismatch {
+                    index,
+                    declared: app.app_slot_number,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that every app descriptor reports the same descriptor_version as the header, catching a partially applied
+    /// update that left a slot written by a different tool version. Reports the lowest offending slot.
+    pub fn descriptor_versions_consistent(&self) -> Result<(), ParseError> {
+        let expected = self.header.descriptor_version;
+        for (slot, app) in (0..).zip(self.iter()) {
+            let app = app?;
+            if app.descriptor_version != expected {
+                return Err(ParseError::VersionSkew {
+                    slot,
+                    found: app.descriptor_version,
+                    expected,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the first two region indices whose descriptors declare the same app_slot_number, or None if every declared slot
+    /// number is unique. Quadratic in the slot count, which is expected to be small.
+    pub fn find_duplicate_slot_numbers(&self) -> Option<(u32, u32)> {
+        (0..self.header.num_app_slots).find_map(|second| {
+            let declared = self.get_app_at_slot(second).ok()?.app_slot_number;
+            (0..second)
+                .find(|&first| {
+                    self.get_app_at_slot(first)
+                        .is_ok_and(|app| app.app_slot_number == declared)
+                })
+                .map(|first| (first, second))
+        })
+    }
+
+    /// Check that no app descriptor sets flags outside APP_IMAGE_FLAG_ALL, which a newer tool may have set expecting
+    /// behavior this bootloader doesn't implement. Reports the lowest offending slot.
+    pub fn check_unknown_flags(&self) -> Result<(), ParseError> {
+        for (slot, app) in (0..).zip(self.iter()) {
+            let app = app?;
+            if app.unknown_flags() != 0 {
+                return Err(ParseError::UnknownFlags {
+                    slot,
+                    bits: app.unknown_flags(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that copying the active app image to its execution address won't write into any reserved (address, size)
+    /// region, such as the bootloader's own stack, vector table, or the descriptor region. XIP images never conflict.
+    pub fn check_execution_safe(&self, reserved: &[(u32, u32)]) -> Result<(), ParseError> {
+        let active = self.get_active_slot();
+        if !active.is_copy_to_execution() {
+            return Ok(());
+        }
+
+        // widen so reserved regions touching the top of the address space can't overflow
+        let start = active.execution_address as u64;
+        let end = start + active.execution_copy_size_bytes as u64;
+
+        let conflict = reserved.iter().any(|&(address, size)| {
+            let reserved_start = address as u64;
+            let reserved_end = reserved_start + size as u64;
+            start < reserved_end && reserved_start < end
+        });
+
+        if conflict {
+            Err(ParseError::ExecutionRegionConflict {
+                slot: self.header.active_app_slot,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Request the app image descriptor with the greatest app_version, preferring the lowest slot on ties. Fails with the
+    /// first slot's error if any slot no longer validates.
+    pub fn highest_version_slot(&self) -> Result<AppImageDescriptor, ParseError> {
+        self.max_slot_by(|app| app.app_version)
+    }
+
+    /// Request the app image descriptor with the greatest security_version, preferring the lowest slot on ties. Unlike
+    /// best_slot, app_version is not considered. Fails with the first slot's error if any slot no longer validates.
+    pub fn highest_security_version_slot(&self) -> Result<AppImageDescriptor, ParseError> {
+        self.max_slot_by(|app| app.security_version)
+    }
+
+    /// Request the app image descriptor with the greatest security_version, then the greatest app_version among those, as
+    /// ordered by SlotCandidate. Prefers the lowest slot on ties. Fails with the first slot's error if any slot no longer
+    /// validates.
+    pub fn best_slot(&self) -> Result<AppImageDescriptor, ParseError> {
+        self.max_slot_by(|&app| SlotCandidate(app))
+    }
+
+    /// The app image descriptor with the greatest key, preferring the lowest slot on ties
+    fn max_slot_by<K: Ord>(&self, key: impl Fn(&AppImageDescriptor) -> K) -> Result<AppImageDescriptor, ParseError> {
+        let mut apps = self.iter();
+        // can't fail as there is always at least one slot
+        let mut best = apps.next().unwrap()?;
+        for app in apps {
+            let app = app?;
+            if key(&app) > key(&best) {
+                best = app;
+            }
+        }
+
+        Ok(best)
+    }
+
+    /// CRC32 (DESCRIPTOR_CRC_ALGORITHM) over the header followed by every app descriptor, each without its trailing CRC,
+    /// for attesting the whole region against a known-good layout. region is laid out as for from_bytes, passed explicitly
+    /// so no memory mapped reads are needed; any gap between the header and the app descriptors isn't covered.
+    pub fn region_digest(&self, region: &[u8]) -> Result<u32, ParseError> {
+        let offset = self.header.app_descriptor_base_address as usize;
+        let end = (self.header.num_app_slots as usize)
+            .checked_mul(APP_IMAGE_DESCRIPTOR_SIZE)
+            .and_then(|size| size.checked_add(offset))
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        let needed = end.max(BOOT_REGION_DESCRIPTOR_SIZE);
+        if region.len() < needed {
+            return Err(ParseError::BufferTooSmall {
+                needed,
+                got: region.len(),
+            });
+        }
+
+        // only feed each descriptor's CRC covered bytes: a CRC over a block ending in its own CRC always lands on the same
+        // residue, which would make every valid region digest identically
+        let crc = Crc::<u32>::new(&DESCRIPTOR_CRC_ALGORITHM);
+        let mut digest = crc.digest();
+        let (start, len) = BootableRegionDescriptorHeader::crc_covered_range();
+        digest.update(&region[start..start + len]);
+        let (start, len) = AppImageDescriptor::crc_covered_range();
+        for app in region[offset..end].chunks_exact(APP_IMAGE_DESCRIPTOR_SIZE) {
+            digest.update(&app[start..start + len]);
+        }
+        Ok(digest.finalize())
+    }
+
+    /// Write a human readable summary of the header and every slot's key fields to w, one line each, without needing serde
+    /// or Debug on packed structs. Slots are re-read, so a descriptor that no longer validates is reported with its error.
+    pub fn write_report<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let header = self.header;
+        writeln!(
+            w,
+            "header   version {:#010x} slots {:>3} active {:>3} app descriptors {:#010x} crc {}",
+            { header.descriptor_version },
+            { header.num_app_slots },
+            { header.active_app_slot },
+            { header.app_descriptor_base_address },
+            if header.is_crc_valid() { "ok" } else { "BAD" },
+        )?;
+
+        for slot in 0..header.num_app_slots {
+            let marker = if slot == header.active_app_slot {
+                " (active)"
+            } else {
+                ""
+            };
+            match self.get_app_at_slot(slot) {
+                Ok(app) => writeln!(
+                    w,
+                    "slot {:>3} version {:#010x} security {:>5} flags {:#010x} stored {:#010x}+{:#010x} execution {:#010x}+{:#010x} crc ok{}",
+                    slot,
+                    { app.app_version },
+                    { app.security_version },
+                    { app.flags },
+                    { app.stored_address },
+                    { app.image_size_bytes },
+                    { app.execution_address },
+                    { app.execution_copy_size_bytes },
+                    marker,
+                )?,
+                Err(err) => writeln!(w, "slot {slot:>3} invalid: {err}{marker}")?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Iterate over every app image descriptor in slot order, re-reading and re-validating each. A slot that no longer
+    /// validates, or can't be read through a flash reader, yields its error without ending the iteration.
+    pub fn iter(&self) -> impl Iterator<Item = Result<AppImageDescriptor, ParseError>> + '_ {
+        (0..self.header.num_app_slots).map(|slot| self.get_app_at_slot(slot))
+    }
+}
+
+impl BootableRegionDescriptorHeader {
+    /// Attempt to load a bootable region descriptor header from provided address, which must be 4 byte aligned as descriptors
+    /// on flash always are. Misaligned addresses are caught by a debug assertion.
+    pub fn from_address(address: *const u32) -> Result<BootableRegionDescriptorHeader, ParseError> {
+        debug_assert!(
+            address.is_aligned(),
+            "descriptor header address {address:p} is not 4 byte aligned"
+        );
+        BootableRegionDescriptorHeader::from_bytes(unsafe {
+            core::slice::from_raw_parts(address as *const u8, BOOT_REGION_DESCRIPTOR_SIZE)
+        })
+    }
+
+    /// Attempt to load a bootable region descriptor header from provided address, additionally rejecting headers whose
+    /// descriptor_version is not compatible with DESCRIPTOR_VERSION by DescriptorVersion::is_compatible_with: the major
+    /// fields must match, and the minor fields too while major is 0. Patch differences are allowed.
+    pub fn from_address_checked(address: *const u32) -> Result<BootableRegionDescriptorHeader, ParseError> {
+        let header = BootableRegionDescriptorHeader::from_address(address)?;
+        let found = header.descriptor_version;
+
+        if !DescriptorVersion::CURRENT.is_compatible_with(&DescriptorVersion::from_u32(found)) {
+            Err(ParseError::IncompatibleVersion {
+                found,
+                expected: DESCRIPTOR_VERSION,
+            })
+        } else {
+            Ok(header)
+        }
+    }
+
+    /// Attempt to load a bootable region descriptor header in the on-flash (little endian) format from the start of buf.
+    /// buf does not need to be aligned.
+    pub fn from_bytes(buf: &[u8]) -> Result<BootableRegionDescriptorHeader, ParseError> {
+        BootableRegionDescriptorHeader::from_bytes_endian(buf, Endianness::ON_FLASH)
+    }
+
+    /// Attempt to load a bootable region descriptor header encoded in byte order endian from the start of buf
+    pub fn from_bytes_endian(buf: &[u8], endian: Endianness) -> Result<BootableRegionDescriptorHeader, ParseError> {
+        BootableRegionDescriptorHeader::from_encoded_bytes(buf, endian.swaps())
+    }
+
+    /// Attempt to load a little endian encoded bootable region descriptor header from the start of buf
+    #[deprecated(note = "use from_bytes_endian(buf, Endianness::Little)")]
+    pub fn from_bytes_le(buf: &[u8]) -> Result<BootableRegionDescriptorHeader, ParseError> {
+        BootableRegionDescriptorHeader::from_bytes_endian(buf, Endianness::Little)
+    }
+
+    /// Attempt to load a big endian encoded bootable region descriptor header from the start of buf
+    #[deprecated(note = "use from_bytes_endian(buf, Endianness::Big)")]
+    pub fn from_bytes_be(buf: &[u8]) -> Result<BootableRegionDescriptorHeader, ParseError> {
+        BootableRegionDescriptorHeader::from_bytes_endian(buf, Endianness::Big)
+    }
+
+    /// UNVALIDATED: decode a header in the on-flash (little endian) format from the start of buf, checking only that buf is
+    /// long enough. Signature, CRC, sizes and slot fields are returned as found, for repair tooling that needs to inspect a
+    /// damaged header. Never boot from the result.
+    pub fn read_unchecked(buf: &[u8]) -> Result<BootableRegionDescriptorHeader, ParseError> {
+        let bytes = buf
+            .get(..BOOT_REGION_DESCRIPTOR_SIZE)
+            .ok_or(ParseError::BufferTooSmall {
+                needed: BOOT_REGION_DESCRIPTOR_SIZE,
+                got: buf.len(),
+            })?;
+
+        // to_le is its own inverse, so it also decodes from the on-flash format
+        Ok(bytemuck::pod_read_unaligned::<BootableRegionDescriptorHeader>(bytes).to_le())
+    }
+
+    /// Read the header from buf, byte swapping every field if the encoding differs from the host
+    fn from_encoded_bytes(buf: &[u8], swap: bool) -> Result<BootableRegionDescriptorHeader, ParseError> {
+        let bytes = buf
+            .get(..BOOT_REGION_DESCRIPTOR_SIZE)
+            .ok_or(ParseError::BufferTooSmall {
+                needed: BOOT_REGION_DESCRIPTOR_SIZE,
+                got: buf.len(),
+            })?;
+        let mut unvalidated: BootableRegionDescriptorHeader = bytemuck::pod_read_unaligned(bytes);
+        if swap {
+            unvalidated = unvalidated.byte_swapped();
+        }
+
+        if is_erased(bytes) {
+            return Err(ParseError::ErasedRegion);
+        }
+        unvalidated.validate()?;
+
+        Ok(unvalidated)
+    }
+
+    /// Run every check from_bytes applies to a decoded header on this already decoded one, for checking headers built or
+    /// edited in memory without encoding them first: signature, header_crc, both descriptor sizes, and that active_app_slot
+    /// is one of num_app_slots (at least 1) slots
+    pub fn validate(&self) -> Result<(), ParseError> {
+        if self.signature != BOOT_REGION_DESCRIPTOR_SIGNATURE {
+            Err(ParseError::InvalidSignature)
+        } else if !self.is_crc_valid() {
+            Err(ParseError::InvalidHeaderCrc {
+                found: self.stored_crc(),
+                expected: self.compute_crc(),
+            })
+        } else if self.descriptor_header_size_bytes != BOOT_REGION_DESCRIPTOR_SIZE as u32 {
+            Err(ParseError::SizeMismatch {
+                field: SizeField::DescriptorHeaderSize,
+                found: self.descriptor_header_size_bytes,
+                expected: BOOT_REGION_DESCRIPTOR_SIZE as u32,
+            })
+        } else if self.app_descriptor_size_bytes != APP_IMAGE_DESCRIPTOR_SIZE as u32 {
+            Err(ParseError::SizeMismatch {
+                field: SizeField::AppDescriptorSize,
+                found: self.app_descriptor_size_bytes,
+                expected: APP_IMAGE_DESCRIPTOR_SIZE as u32,
+            })
+        } else if self.num_app_slots < 1 {
+            Err(ParseError::InvalidSlotCount)
+        } else if self.active_app_slot >= self.num_app_slots {
+            Err(ParseError::InvalidAppSlot)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Scan buf at every multiple of align for BOOT_REGION_DESCRIPTOR_SIGNATURE, then validate the header at the first hit.
+    /// Returns the byte offset of the header within buf along with the parsed header.
+    pub fn find_in(buf: &[u8], align: usize) -> Result<(usize, BootableRegionDescriptorHeader), ParseError> {
+        let signature = BOOT_REGION_DESCRIPTOR_SIGNATURE.to_le_bytes();
+
+        let offset = (0..buf.len())
+            .step_by(align.max(1))
+            .take_while(|offset| buf.len() - offset >= BOOT_REGION_DESCRIPTOR_SIZE)
+            .find(|&offset| buf[offset..].starts_with(&signature))
+            .ok_or(ParseError::InvalidSignature)?;
+
+        Ok((offset, BootableRegionDescriptorHeader::from_bytes(&buf[offset..])?))
+    }
+
+    /// Generate at compile time a descriptor region header. Useful for initialization and explicit linker placement for debug scenarios.
+    /// header_crc is computed with CRC_32_ISO_HDLC.
+    pub const fn new(
+        app_slot_count: u32,
         active_app_slot: u32,
         app_descriptor_address: u32,
     ) -> BootableRegionDescriptorHeader {
@@ -288,185 +1692,4587 @@ impl BootableRegionDescriptorHeader {
             app_descriptor_base_address: app_descriptor_address,
             num_app_slots: app_slot_count,
             active_app_slot,
+            reserved_bytes: 0,
+            sequence: 0,
             header_crc: 0,
         };
 
-        this.header_crc = this.compute_crc();
+        this.header_crc = this.compute_crc();
+
+        this
+    }
+
+    /// Reserve reserved_bytes after the last app descriptor and recompute header_crc, for chaining onto new in a const
+    pub const fn with_reserved_bytes(mut self, reserved_bytes: u32) -> BootableRegionDescriptorHeader {
+        self.reserved_bytes = reserved_bytes;
+        self.header_crc = self.compute_crc();
+        self
+    }
+
+    /// Set sequence and recompute header_crc, for chaining onto new in a const
+    pub const fn with_sequence(mut self, sequence: u32) -> BootableRegionDescriptorHeader {
+        self.sequence = sequence;
+        self.header_crc = self.compute_crc();
+        self
+    }
+
+    /// Check if this header was written after other, comparing sequence as a serial number (RFC 1982) so that ordering
+    /// survives wrapping: 0 is newer than u32::MAX. Headers more than 2^31 rewrites apart compare the wrong way round.
+    pub const fn is_newer_than(&self, other: &BootableRegionDescriptorHeader) -> bool {
+        (self.sequence.wrapping_sub(other.sequence) as i32) > 0
+    }
+
+    /// Increment sequence, wrapping from u32::MAX to 0, and recompute header_crc
+    fn bump_sequence(&mut self) {
+        self.sequence = self.sequence.wrapping_add(1);
+        self.header_crc = self.compute_crc();
+    }
+
+    /// Generate a header for a region that will be filled in slot by slot, with slot 0 active.
+    /// header_crc is computed with CRC_32_ISO_HDLC.
+    pub const fn empty(num_app_slots: u32, app_descriptor_address: u32) -> BootableRegionDescriptorHeader {
+        BootableRegionDescriptorHeader::new(num_app_slots, 0, app_descriptor_address)
+    }
+
+    /// Mark slot as the active app slot and recompute header_crc. Leaves the header untouched if slot is out of range.
+    pub fn set_active_slot(&mut self, slot: u32) -> Result<(), ParseError> {
+        if slot >= self.num_app_slots {
+            return Err(ParseError::InvalidAppSlot);
+        }
+
+        self.active_app_slot = slot;
+        self.header_crc = self.compute_crc();
+
+        Ok(())
+    }
+
+    /// Total bytes occupied by this header plus all app descriptors and the reserved_bytes after them, using the header's
+    /// self-reported sizes so the result matches the layout of the descriptor version that wrote it. Does not include any
+    /// gap between the header and the app descriptors.
+    pub fn region_size(&self) -> Result<usize, ParseError> {
+        (self.num_app_slots as usize)
+            .checked_mul(self.app_descriptor_size_bytes as usize)
+            .and_then(|size| size.checked_add(self.descriptor_header_size_bytes as usize))
+            .and_then(|size| size.checked_add(self.reserved_bytes as usize))
+            .ok_or(ParseError::ArithmeticOverflow)
+    }
+
+    /// Byte offset of slot's app descriptor within a region laid out as for BootableRegionDescriptors::from_bytes, with
+    /// app_descriptor_base_address treated as an offset and descriptors spaced by the self-reported app_descriptor_size_bytes
+    pub fn descriptor_offset(&self, slot: u32) -> Result<usize, ParseError> {
+        if slot >= self.num_app_slots {
+            return Err(ParseError::InvalidAppSlot);
+        }
+
+        (slot as usize)
+            .checked_mul(self.app_descriptor_size_bytes as usize)
+            .and_then(|offset| offset.checked_add(self.app_descriptor_base_address as usize))
+            .ok_or(ParseError::ArithmeticOverflow)
+    }
+
+    /// Check that num_app_slots is at most max_slots, before any app descriptor is read
+    pub fn validate_slot_count(&self, max_slots: u32) -> Result<(), ParseError> {
+        if self.num_app_slots > max_slots {
+            Err(ParseError::TooManySlots {
+                found: self.num_app_slots,
+                max: max_slots,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Spacing in bytes between consecutive app descriptors, the self-reported app_descriptor_size_bytes. Descriptors are
+    /// packed back to back, there is no padding between slots.
+    pub const fn slot_stride(&self) -> u32 {
+        self.app_descriptor_size_bytes
+    }
+
+    /// Check that the app descriptor array, num_app_slots descriptors spaced by slot_stride from
+    /// app_descriptor_base_address, doesn't overlap this header when it lives at header_address. Descriptors may sit before
+    /// or after the header, with any gap.
+    pub fn validate_descriptor_base(&self, header_address: usize) -> Result<(), ParseError> {
+        let header_start = header_address as u64;
+        let header_end = header_start.saturating_add(BOOT_REGION_DESCRIPTOR_SIZE as u64);
+        let base = self.app_descriptor_base_address as u64;
+        let end = base + self.num_app_slots as u64 * self.slot_stride() as u64;
+
+        if base < header_end && header_start < end {
+            Err(ParseError::DescriptorBaseOverlapsHeader)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Byte offset of the active slot's app descriptor, see descriptor_offset
+    pub fn active_descriptor_offset(&self) -> Result<usize, ParseError> {
+        self.descriptor_offset(self.active_app_slot)
+    }
+
+    /// Return this struct's contents as a slice
+    pub const fn as_bytes(&self) -> &[u8] {
+        bytes_of(self)
+    }
+
+    /// Return this struct's contents as a mutable slice for patching fields in place. header_crc is not updated, call
+    /// recompute_crc after editing.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::bytes_of_mut(self)
+    }
+
+    /// Update header_crc to match the current contents, required after changing any field
+    pub fn recompute_crc(&mut self) {
+        self.header_crc = self.compute_crc();
+    }
+
+    /// Copy this header in the on-flash (little endian) format into the start of buf, returning the number of bytes written
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        self.write_to_endian(buf, Endianness::ON_FLASH)
+    }
+
+    /// Copy this header encoded in byte order endian into the start of buf, returning the number of bytes written
+    pub fn write_to_endian(&self, buf: &mut [u8], endian: Endianness) -> Result<usize, ParseError> {
+        let encoded = if endian.swaps() { self.byte_swapped() } else { *self };
+        write_bytes(encoded.as_bytes(), buf)
+    }
+
+    /// Copy this header little endian encoded into the start of buf, returning the number of bytes written
+    #[deprecated(note = "use write_to_endian(buf, Endianness::Little)")]
+    pub fn write_to_le(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        self.write_to_endian(buf, Endianness::Little)
+    }
+
+    /// Copy this header big endian encoded into the start of buf, returning the number of bytes written
+    #[deprecated(note = "use write_to_endian(buf, Endianness::Big)")]
+    pub fn write_to_be(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        self.write_to_endian(buf, Endianness::Big)
+    }
+
+    /// This header with every field byte swapped
+    const fn byte_swapped(self) -> BootableRegionDescriptorHeader {
+        BootableRegionDescriptorHeader {
+            signature: self.signature.swap_bytes(),
+            descriptor_version: self.descriptor_version.swap_bytes(),
+            descriptor_header_size_bytes: self.descriptor_header_size_bytes.swap_bytes(),
+            app_descriptor_size_bytes: self.app_descriptor_size_bytes.swap_bytes(),
+            app_descriptor_base_address: self.app_descriptor_base_address.swap_bytes(),
+            num_app_slots: self.num_app_slots.swap_bytes(),
+            active_app_slot: self.active_app_slot.swap_bytes(),
+            reserved_bytes: self.reserved_bytes.swap_bytes(),
+            sequence: self.sequence.swap_bytes(),
+            header_crc: self.header_crc.swap_bytes(),
+        }
+    }
+
+    /// This header with every field in little endian byte order
+    const fn to_le(self) -> BootableRegionDescriptorHeader {
+        if cfg!(target_endian = "big") {
+            self.byte_swapped()
+        } else {
+            self
+        }
+    }
+
+    /// Return the CRC32 checksum over the current contents of this struct, as encoded on flash (little endian)
+    pub const fn compute_crc(&self) -> u32 {
+        self.compute_crc_with(&DESCRIPTOR_CRC_ALGORITHM)
+    }
+
+    /// Return the checksum under algorithm over the current contents of this header, as encoded on flash (little endian).
+    /// Useful for matching the CRC unit of a chip whose ROM doesn't implement CRC_32_ISO_HDLC.
+    pub const fn compute_crc_with(&self, algorithm: &'static Algorithm<u32>) -> u32 {
+        checksum_without_crc(bytes_of(&self.to_le()), algorithm)
+    }
+
+    /// Byte range (start, len) within the on-flash header covered by header_crc: everything but the trailing header_crc.
+    /// Useful for feeding exactly the protected bytes into an external hash engine for measured boot.
+    pub const fn crc_covered_range() -> (usize, usize) {
+        (0, BOOT_REGION_DESCRIPTOR_SIZE - size_of::<u32>())
+    }
+
+    /// The header_crc stored in this header, copied out of the packed struct so callers never take a reference to it
+    pub const fn stored_crc(&self) -> u32 {
+        self.header_crc
+    }
+
+    /// Check if the header_crc value matches the current computed CRC32 checksum
+    pub const fn is_crc_valid(&self) -> bool {
+        self.is_crc_valid_with(&DESCRIPTOR_CRC_ALGORITHM)
+    }
+
+    /// Check if the header_crc value matches the current contents' checksum under algorithm
+    pub const fn is_crc_valid_with(&self, algorithm: &'static Algorithm<u32>) -> bool {
+        self.stored_crc() == self.compute_crc_with(algorithm)
+    }
+}
+
+impl AppImageDescriptor {
+    /// Start building an app image descriptor, see AppImageDescriptorBuilder
+    pub fn builder() -> AppImageDescriptorBuilder {
+        AppImageDescriptorBuilder::new()
+    }
+
+    /// Build the descriptor for image in one step with its image CRC, so the two can't drift apart. image_size_bytes is
+    /// set to image.len(), overriding any size given to the builder, and so is the copy size of an image copied to its
+    /// execution address. Returns the descriptor and the CRC32 checksum over image, which the caller places at
+    /// stored_crc_address. Fails with ArithmeticOverflow if image is 4GB or larger, and with UnsupportedIntegrityType
+    /// unless the builder's integrity_type is INTEGRITY_CRC32.
+    pub fn with_computed_image_crc(
+        builder: AppImageDescriptorBuilder,
+        image: &[u8],
+    ) -> Result<(AppImageDescriptor, u32), ParseError> {
+        let image_size_bytes = u32::try_from(image.len()).map_err(|_| ParseError::ArithmeticOverflow)?;
+        let descriptor = AppImageDescriptorBuilder {
+            image_size_bytes,
+            execution: builder.execution.map(|(address, _)| (address, image_size_bytes)),
+            ..builder
+        }
+        .build();
+        descriptor.check_integrity_type()?;
+
+        let mut digest = descriptor.image_digest();
+        digest.update(image);
+        Ok((descriptor, digest.finalize()))
+    }
+
+    /// Attempt to read app_slot AppImageDescriptor from app_descriptors_address_start, which must be 4 byte aligned.
+    /// Misaligned addresses are caught by a debug assertion.
+    pub fn from_region(
+        app_descriptors_address_start: *const u32,
+        app_slot: u32,
+    ) -> Result<AppImageDescriptor, ParseError> {
+        debug_assert!(
+            app_descriptors_address_start.is_aligned(),
+            "app descriptors address {app_descriptors_address_start:p} is not 4 byte aligned"
+        );
+        AppImageDescriptor::from_region_bytes(app_descriptors_address_start as *const u8, app_slot)
+    }
+
+    /// from_region without the alignment requirement, for app descriptors inside a byte buffer
+    fn from_region_bytes(
+        app_descriptors_address_start: *const u8,
+        app_slot: u32,
+    ) -> Result<AppImageDescriptor, ParseError> {
+        let offset = (app_slot as usize)
+            .checked_mul(APP_IMAGE_DESCRIPTOR_SIZE)
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        let address = unsafe { app_descriptors_address_start.add(offset) };
+
+        AppImageDescriptor::from_encoded_bytes(
+            unsafe { core::slice::from_raw_parts(address, APP_IMAGE_DESCRIPTOR_SIZE) },
+            cfg!(target_endian = "big"),
+            Some(app_slot),
+        )
+    }
+
+    /// Generate a non-copied (XIP: execute in place) app image descriptor with the given parameters.
+    /// descriptor_crc is computed with CRC_32_ISO_HDLC.
+    pub const fn new_execute_in_place_image(
+        slot: u32,
+        app_version: u32,
+        security_version: u32,
+        flags: u32,
+        stored_address: u32,
+        image_size_bytes: u32,
+        stored_crc_address: u32,
+    ) -> AppImageDescriptor {
+        let mut app_image_descriptor = Self {
+            descriptor_version: DESCRIPTOR_VERSION,
+            app_slot_number: slot,
+            app_version,
+            security_version,
+            flags,
+            stored_address,
+            image_size_bytes,
+            stored_crc_address,
+            execution_address: stored_address,
+            execution_copy_size_bytes: 0,
+            boot_attempts: 0,
+            name: [0; APP_IMAGE_NAME_SIZE],
+            integrity_type: INTEGRITY_CRC32,
+            descriptor_crc: 0,
+        };
+
+        app_image_descriptor.descriptor_crc = app_image_descriptor.compute_crc();
+
+        app_image_descriptor
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Generate a copied to RAM app image descriptor with given parameters.
+    /// descriptor_crc is computed with CRC_32_ISO_HDLC.
+    pub const fn new_ram_image(
+        slot: u32,
+        app_version: u32,
+        security_version: u32,
+        flags: u32,
+        flash_address: u32,
+        image_size_bytes: u32,
+        ram_address: u32,
+        stored_crc_address: u32,
+    ) -> Self {
+        let mut app_image_descriptor = Self {
+            descriptor_version: DESCRIPTOR_VERSION,
+            app_slot_number: slot,
+            app_version,
+            security_version,
+            flags: flags | APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS,
+            stored_address: flash_address,
+            image_size_bytes,
+            stored_crc_address,
+            execution_address: ram_address,
+            execution_copy_size_bytes: image_size_bytes,
+            boot_attempts: 0,
+            name: [0; APP_IMAGE_NAME_SIZE],
+            integrity_type: INTEGRITY_CRC32,
+            descriptor_crc: 0,
+        };
+
+        app_image_descriptor.descriptor_crc = app_image_descriptor.compute_crc();
+
+        app_image_descriptor
+    }
+
+    /// Attempt to interpret address memory contents as an AppImageDescriptor. address must be 4 byte aligned, misaligned
+    /// addresses are caught by a debug assertion.
+    pub fn from_address(address: *const u32) -> Result<AppImageDescriptor, ParseError> {
+        debug_assert!(
+            address.is_aligned(),
+            "app descriptor address {address:p} is not 4 byte aligned"
+        );
+        AppImageDescriptor::from_bytes(unsafe {
+            core::slice::from_raw_parts(address as *const u8, APP_IMAGE_DESCRIPTOR_SIZE)
+        })
+    }
+
+    /// Attempt to interpret the start of buf as an AppImageDescriptor in the on-flash (little endian) format.
+    /// buf does not need to be aligned.
+    pub fn from_bytes(buf: &[u8]) -> Result<AppImageDescriptor, ParseError> {
+        AppImageDescriptor::from_bytes_endian(buf, Endianness::ON_FLASH)
+    }
+
+    /// Attempt to interpret the start of buf as an AppImageDescriptor encoded in byte order endian
+    pub fn from_bytes_endian(buf: &[u8], endian: Endianness) -> Result<AppImageDescriptor, ParseError> {
+        AppImageDescriptor::from_encoded_bytes(buf, endian.swaps(), None)
+    }
+
+    /// Attempt to interpret the start of buf as a little endian encoded AppImageDescriptor
+    #[deprecated(note = "use from_bytes_endian(buf, Endianness::Little)")]
+    pub fn from_bytes_le(buf: &[u8]) -> Result<AppImageDescriptor, ParseError> {
+        AppImageDescriptor::from_bytes_endian(buf, Endianness::Little)
+    }
+
+    /// Attempt to interpret the start of buf as a big endian encoded AppImageDescriptor
+    #[deprecated(note = "use from_bytes_endian(buf, Endianness::Big)")]
+    pub fn from_bytes_be(buf: &[u8]) -> Result<AppImageDescriptor, ParseError> {
+        AppImageDescriptor::from_bytes_endian(buf, Endianness::Big)
+    }
+
+    /// Read the descriptor from buf, byte swapping every field if the encoding differs from the host.
+    /// slot is the slot index the descriptor was read from if known, otherwise errors report the declared app_slot_number.
+    fn from_encoded_bytes(buf: &[u8], swap: bool, slot: Option<u32>) -> Result<AppImageDescriptor, ParseError> {
+        let bytes = buf.get(..APP_IMAGE_DESCRIPTOR_SIZE).ok_or(ParseError::BufferTooSmall {
+            needed: APP_IMAGE_DESCRIPTOR_SIZE,
+            got: buf.len(),
+        })?;
+        let mut unvalidated: AppImageDescriptor = bytemuck::pod_read_unaligned(bytes);
+        if swap {
+            unvalidated = unvalidated.byte_swapped();
+        }
+
+        unvalidated.validate_at(slot.unwrap_or(unvalidated.app_slot_number), buf.as_ptr() as usize)?;
+
+        Ok(unvalidated)
+    }
+
+    /// Run every check from_bytes applies to a decoded descriptor on this already decoded one, for checking descriptors
+    /// built or edited in memory without encoding them first: descriptor_crc, that neither the stored nor execution range
+    /// overflows, and that the image isn't empty. Errors report app_slot_number as the slot, and as the descriptor wasn't
+    /// read from flash an InvalidAppCrc reports address 0.
+    pub fn validate(&self) -> Result<(), ParseError> {
+        self.validate_at(self.app_slot_number, 0)
+    }
+
+    /// validate, reporting slot and address in errors as where the descriptor was read from
+    fn validate_at(&self, slot: u32, address: usize) -> Result<(), ParseError> {
+        if !self.is_crc_valid() {
+            Err(ParseError::InvalidAppCrc {
+                slot,
+                address,
+                found: self.stored_crc(),
+                expected: self.compute_crc(),
+            })
+        } else if self.stored_end().is_none() || self.execution_end().is_none() {
+            Err(ParseError::AddressOverflow { slot })
+        } else if self.is_empty_image() {
+            Err(ParseError::EmptyImage { slot })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Borrow app descriptor slot of the descriptor array at the start of buf in place, after validating its CRC.
+    /// Fields can then be edited directly, followed by recompute_crc. The view is in host byte order, which only matches the
+    /// on-flash format on little endian hosts.
+    pub fn from_bytes_mut(buf: &mut [u8], slot: u32) -> Result<&mut AppImageDescriptor, ParseError> {
+        let got = buf.len();
+        let offset = (slot as usize)
+            .checked_mul(APP_IMAGE_DESCRIPTOR_SIZE)
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        let end = offset
+            .checked_add(APP_IMAGE_DESCRIPTOR_SIZE)
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        let bytes = buf
+            .get_mut(offset..end)
+            .ok_or(ParseError::BufferTooSmall { needed: end, got })?;
+
+        let address = bytes.as_ptr() as usize;
+        // can't fail as AppImageDescriptor is packed so any alignment is acceptable and the length is exact
+        let descriptor: &mut AppImageDescriptor = bytemuck::try_from_bytes_mut(bytes).unwrap();
+        if !descriptor.is_crc_valid() {
+            return Err(ParseError::InvalidAppCrc {
+                slot,
+                address,
+                found: descriptor.stored_crc(),
+                expected: descriptor.compute_crc(),
+            });
+        }
+
+        Ok(descriptor)
+    }
+
+    /// Update descriptor_crc to match the current contents, required after changing any field
+    pub fn recompute_crc(&mut self) {
+        self.descriptor_crc = self.compute_crc();
+    }
+
+    /// One past the last byte of the stored image, or None if stored_address + image_size_bytes overflows
+    pub const fn stored_end(&self) -> Option<u32> {
+        let stored_address = self.stored_address;
+        stored_address.checked_add(self.image_size_bytes)
+    }
+
+    /// One past the last byte copied to execution_address, or None if execution_address + execution_copy_size_bytes overflows
+    pub const fn execution_end(&self) -> Option<u32> {
+        let execution_address = self.execution_address;
+        execution_address.checked_add(self.execution_copy_size_bytes)
+    }
+
+    /// Check that neither the stored nor execution ranges wrap past the end of the 32-bit address space
+    pub fn validate_ranges(&self) -> Result<(), ParseError> {
+        if self.stored_end().is_none() || self.execution_end().is_none() {
+            Err(ParseError::AddressOverflow {
+                slot: self.app_slot_number,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that the stored image lies entirely within the flash partition \[partition_base, partition_base + partition_size)
+    pub const fn fits_in_partition(&self, partition_base: u32, partition_size: u32) -> bool {
+        // widen so neither end can overflow
+        let stored_start = self.stored_address as u64;
+        let stored_end = stored_start + self.image_size_bytes as u64;
+        let partition_end = partition_base as u64 + partition_size as u64;
+
+        stored_start >= partition_base as u64 && stored_end <= partition_end
+    }
+
+    /// Like fits_in_partition, but returns ImageExceedsPartition for this descriptor's slot if the image doesn't fit
+    pub fn validate_partition(&self, partition_base: u32, partition_size: u32) -> Result<(), ParseError> {
+        if self.fits_in_partition(partition_base, partition_size) {
+            Ok(())
+        } else {
+            Err(ParseError::ImageExceedsPartition {
+                slot: self.app_slot_number,
+            })
+        }
+    }
+
+    /// Check that the 4 byte image CRC at stored_crc_address lies outside the stored image, so the CRC isn't part of the
+    /// data it checks
+    pub const fn crc_address_is_external(&self) -> bool {
+        // widen so neither end can overflow
+        let image_start = self.stored_address as u64;
+        let image_end = image_start + self.image_size_bytes as u64;
+        let crc_start = self.stored_crc_address as u64;
+        let crc_end = crc_start + size_of::<u32>() as u64;
+
+        crc_end <= image_start || image_end <= crc_start
+    }
+
+    /// Like crc_address_is_external, but returns CrcAddressInsideImage for this descriptor's slot if the CRC is inside
+    pub fn validate_crc_address(&self) -> Result<(), ParseError> {
+        if self.crc_address_is_external() {
+            Ok(())
+        } else {
+            Err(ParseError::CrcAddressInsideImage {
+                slot: self.app_slot_number,
+            })
+        }
+    }
+
+    /// Check that an image copied to its execution address is copied whole: with APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS
+    /// set, execution_copy_size_bytes must equal image_size_bytes. Returns InconsistentCopySize for this descriptor's slot
+    /// otherwise. Execute in place images always pass.
+    pub fn copy_is_consistent(&self) -> Result<(), ParseError> {
+        self.copy_is_consistent_within(0)
+    }
+
+    /// Like copy_is_consistent, but allows execution_copy_size_bytes to exceed image_size_bytes by up to tolerance bytes,
+    /// for images padded out to a flash page or cache line. A copy of 0 bytes or one shorter than the image always fails.
+    pub fn copy_is_consistent_within(&self, tolerance: u32) -> Result<(), ParseError> {
+        if !self.is_copy_to_execution() {
+            return Ok(());
+        }
+
+        let copy = self.execution_copy_size_bytes as u64;
+        let image = self.image_size_bytes as u64;
+        if copy == 0 || copy < image || copy > image + tolerance as u64 {
+            Err(ParseError::InconsistentCopySize {
+                slot: self.app_slot_number,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check if this descriptor is all zero apart from a valid descriptor_crc, as from Default: a slot that hasn't been
+    /// populated yet
+    pub fn is_blank(&self) -> bool {
+        *self == AppImageDescriptor::default()
+    }
+
+    /// Check if this descriptor would boot a zero length image: image_size_bytes is 0 without
+    /// APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK set. Blank descriptors are exempt, as they describe no image at all and mark
+    /// slots not yet populated, so a region fresh from init_region still parses. Booting a blank active slot is instead
+    /// rejected by BootableRegionDescriptors::select_boot_slot and boot_or_recovery.
+    pub fn is_empty_image(&self) -> bool {
+        self.image_size_bytes == 0 && !self.is_skip_crc_check() && !self.is_blank()
+    }
+
+    /// Record a boot attempt and recompute descriptor_crc. The bootloader calls this and writes the descriptor back before
+    /// jumping to the image. Saturates rather than wrapping back to zero.
+    pub fn increment_boot_attempts(&mut self) {
+        self.boot_attempts = self.boot_attempts.saturating_add(1);
+        self.recompute_crc();
+    }
+
+    /// Reset boot_attempts and recompute descriptor_crc. The app calls this and writes the descriptor back once it has
+    /// booted successfully.
+    pub fn clear_boot_attempts(&mut self) {
+        self.boot_attempts = 0;
+        self.recompute_crc();
+    }
+
+    /// Check if boot_attempts has reached max, meaning the image has failed to confirm a successful boot max times and the
+    /// bootloader should fall back to another slot
+    pub const fn boot_attempts_exceeded(&self, max: u32) -> bool {
+        self.boot_attempts >= max
+    }
+
+    /// Check if every bit in flag is set in this descriptor's flags
+    pub const fn has_flag(&self, flag: u32) -> bool {
+        // copy out of the packed struct before bit testing
+        let flags = self.flags;
+        flags & flag == flag
+    }
+
+    /// Check if APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS is set
+    pub const fn is_copy_to_execution(&self) -> bool {
+        self.has_flag(APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS)
+    }
+
+    /// Check if APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK is set
+    pub const fn is_skip_crc_check(&self) -> bool {
+        self.has_flag(APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK)
+    }
+
+    /// Check if APP_IMAGE_FLAG_RECOVERY is set
+    pub const fn is_recovery(&self) -> bool {
+        self.has_flag(APP_IMAGE_FLAG_RECOVERY)
+    }
+
+    /// Check if APP_IMAGE_FLAG_ENCRYPTED is set. This crate doesn't decrypt, the image CRC covers the ciphertext in flash.
+    pub const fn is_encrypted(&self) -> bool {
+        self.has_flag(APP_IMAGE_FLAG_ENCRYPTED)
+    }
+
+    /// This descriptor's flags as AppImageFlags
+    pub const fn image_flags(&self) -> AppImageFlags {
+        AppImageFlags::from_bits(self.flags)
+    }
+
+    /// Name of each known flag set in this descriptor's flags, without the APP_IMAGE_FLAG_ prefix
+    pub fn flag_names(&self) -> impl Iterator<Item = &'static str> {
+        let flags = self.flags;
+        APP_IMAGE_FLAG_NAMES
+            .iter()
+            .filter(move |(flag, _)| flags & flag != 0)
+            .map(|&(_, name)| name)
+    }
+
+    /// Bits set in flags that don't correspond to any known APP_IMAGE_FLAG_* constant
+    pub fn unknown_flags(&self) -> u32 {
+        let known = APP_IMAGE_FLAG_NAMES
+            .iter()
+            .fold(APP_IMAGE_FLAG_NONE, |known, &(flag, _)| known | flag);
+        self.flags & !known
+    }
+
+    /// The copy to perform before executing this image if APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS is set, or None for XIP
+    /// images. The bootloader performs the copy itself.
+    pub const fn copy_plan(&self) -> Option<CopyPlan> {
+        if self.is_copy_to_execution() {
+            Some(CopyPlan {
+                src: self.stored_address,
+                dst: self.execution_address,
+                len: self.execution_copy_size_bytes,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Boot step 6: where the Cortex-M vector table lives once the image is loaded, the value to program into VTOR and read
+    /// the initial stack pointer and reset vector from. execution_address for copied images, stored_address for XIP.
+    pub const fn vector_table_address(&self) -> u32 {
+        if self.is_copy_to_execution() {
+            self.execution_address
+        } else {
+            self.stored_address
+        }
+    }
+
+    /// Summarize how this image would be loaded and started, see BootSummary
+    pub const fn boot_summary(&self) -> BootSummary {
+        let xip = !self.is_copy_to_execution();
+        BootSummary {
+            xip,
+            load_from: self.stored_address,
+            run_at: self.vector_table_address(),
+            copy_len: if xip { 0 } else { self.execution_copy_size_bytes },
+            crc_checked: !self.is_skip_crc_check(),
+        }
+    }
+
+    /// Check the CRC32 checksum over image\[..image_size_bytes\] against expected, typically read from stored_crc_address.
+    /// Always succeeds if APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK is set. For APP_IMAGE_FLAG_ENCRYPTED images, pass the
+    /// ciphertext as stored, before decryption.
+    pub fn verify_image_crc(&self, image: &[u8], expected: u32) -> Result<(), ParseError> {
+        if self.is_skip_crc_check() {
+            return Ok(());
+        }
+
+        let image_size_bytes = self.image_size_bytes as usize;
+        let image = image.get(..image_size_bytes).ok_or(ParseError::BufferTooSmall {
+            needed: image_size_bytes,
+            got: image.len(),
+        })?;
+
+        let mut digest = self.image_digest();
+        digest.update(image);
+        self.finish_image_digest(digest, expected)
+    }
+
+    /// Boot step 4: check image\[..image_size_bytes\] the way integrity_type says to. For INTEGRITY_CRC32 that's the CRC32
+    /// checksum against stored_crc, the value read from stored_crc_address. Integrity types this build doesn't implement
+    /// fail with UnsupportedIntegrityType. Always succeeds if APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK is set.
+    pub fn validate_image(&self, image: &[u8], stored_crc: u32) -> Result<(), ParseError> {
+        // finish_image_digest dispatches on integrity_type, so every image check path shares it
+        self.verify_image_crc(image, stored_crc)
+    }
+
+    /// Like validate_image, but with stored_address and stored_crc_address treated as byte offsets into region. The
+    /// expected CRC is read from region as a little endian u32. Always succeeds if APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK is
+    /// set, without reading region.
+    pub fn validate_image_in_buffer(&self, region: &[u8]) -> Result<(), ParseError> {
+        if self.is_skip_crc_check() {
+            return Ok(());
+        }
+
+        let stored_address = self.stored_address as usize;
+        let image_end = stored_address
+            .checked_add(self.image_size_bytes as usize)
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        let crc_address = self.stored_crc_address as usize;
+        let crc_end = crc_address
+            .checked_add(size_of::<u32>())
+            .ok_or(ParseError::ArithmeticOverflow)?;
+
+        let needed = image_end.max(crc_end);
+        if region.len() < needed {
+            return Err(ParseError::BufferTooSmall {
+                needed,
+                got: region.len(),
+            });
+        }
+
+        let crc = &region[crc_address..crc_end];
+        let stored_crc = u32::from_le_bytes([crc[0], crc[1], crc[2], crc[3]]);
+        self.validate_image(&region[stored_address..image_end], stored_crc)
+    }
+
+    /// Like verify_image_crc, for an image stored as several non-contiguous segments. The segments are checksummed in order
+    /// as one image, and together must hold at least image_size_bytes. Always succeeds if APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK
+    /// is set.
+    pub fn verify_image_segments<'s>(
+        &self,
+        segments: impl IntoIterator<Item = &'s [u8]>,
+        expected: u32,
+    ) -> Result<(), ParseError> {
+        if self.is_skip_crc_check() {
+            return Ok(());
+        }
+
+        let image_size_bytes = self.image_size_bytes as usize;
+        let mut digest = self.image_digest();
+        let mut got = 0;
+        for segment in segments {
+            let take = segment.len().min(image_size_bytes - got);
+            digest.update(&segment[..take]);
+            got += take;
+        }
+
+        if got < image_size_bytes {
+            return Err(ParseError::BufferTooSmall {
+                needed: image_size_bytes,
+                got,
+            });
+        }
+        self.finish_image_digest(digest, expected)
+    }
+
+    /// Start an incremental CRC32 checksum over the app image, for validating an image read in chunks.
+    /// Feed every chunk of image\[..image_size_bytes\] in order with Digest::update, then call finish_image_digest.
+    pub fn image_digest(&self) -> Digest<'static, u32> {
+        IMAGE_CRC.digest()
+    }
+
+    /// Finalize a digest started with image_digest and compare against expected. Only INTEGRITY_CRC32 images can be
+    /// checked this way, any other integrity_type fails with UnsupportedIntegrityType. Always succeeds if
+    /// APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK is set.
+    pub fn finish_image_digest(&self, digest: Digest<'static, u32>, expected: u32) -> Result<(), ParseError> {
+        if self.is_skip_crc_check() {
+            return Ok(());
+        }
+        self.check_integrity_type()?;
+
+        let found = digest.finalize();
+        if found != expected {
+            Err(ParseError::InvalidImageCrc { found, expected })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check that integrity_type is one this build implements, INTEGRITY_CRC32
+    fn check_integrity_type(&self) -> Result<(), ParseError> {
+        match self.integrity_type {
+            INTEGRITY_CRC32 => Ok(()),
+            integrity_type => Err(ParseError::UnsupportedIntegrityType {
+                slot: self.app_slot_number,
+                integrity_type,
+            }),
+        }
+    }
+
+    /// Return this structure as a slice
+    pub const fn as_bytes(&self) -> &[u8] {
+        bytes_of(self)
+    }
+
+    /// Return this structure as a mutable slice for patching fields in place. descriptor_crc is not updated, call
+    /// recompute_crc after editing.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::bytes_of_mut(self)
+    }
+
+    /// Copy this descriptor in the on-flash (little endian) format into the start of buf, returning the number of bytes written
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        self.write_to_endian(buf, Endianness::ON_FLASH)
+    }
+
+    /// Copy this descriptor encoded in byte order endian into the start of buf, returning the number of bytes written
+    pub fn write_to_endian(&self, buf: &mut [u8], endian: Endianness) -> Result<usize, ParseError> {
+        let encoded = if endian.swaps() { self.byte_swapped() } else { *self };
+        write_bytes(encoded.as_bytes(), buf)
+    }
+
+    /// Copy this descriptor little endian encoded into the start of buf, returning the number of bytes written
+    #[deprecated(note = "use write_to_endian(buf, Endianness::Little)")]
+    pub fn write_to_le(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        self.write_to_endian(buf, Endianness::Little)
+    }
+
+    /// Copy this descriptor big endian encoded into the start of buf, returning the number of bytes written
+    #[deprecated(note = "use write_to_endian(buf, Endianness::Big)")]
+    pub fn write_to_be(&self, buf: &mut [u8]) -> Result<usize, ParseError> {
+        self.write_to_endian(buf, Endianness::Big)
+    }
+
+    /// Copy this descriptor in the on-flash format into slot of a region laid out as described by header, with the header
+    /// at offset 0 as for BootableRegionDescriptors::from_bytes. Together with the header's write_to this builds a
+    /// complete region without any offset math in the caller.
+    pub fn write_to_region(
+        &self,
+        region: &mut [u8],
+        header: &BootableRegionDescriptorHeader,
+        slot: u32,
+    ) -> Result<(), ParseError> {
+        let offset = header.descriptor_offset(slot)?;
+        let got = region.len();
+        let bytes = offset
+            .checked_add(APP_IMAGE_DESCRIPTOR_SIZE)
+            .and_then(|end| region.get_mut(offset..end))
+            .ok_or(ParseError::BufferTooSmall {
+                needed: offset.saturating_add(APP_IMAGE_DESCRIPTOR_SIZE),
+                got,
+            })?;
+
+        self.write_to(bytes)?;
+        Ok(())
+    }
+
+    /// This descriptor with every field byte swapped
+    const fn byte_swapped(self) -> AppImageDescriptor {
+        AppImageDescriptor {
+            descriptor_version: self.descriptor_version.swap_bytes(),
+            app_slot_number: self.app_slot_number.swap_bytes(),
+            app_version: self.app_version.swap_bytes(),
+            security_version: self.security_version.swap_bytes(),
+            flags: self.flags.swap_bytes(),
+            stored_address: self.stored_address.swap_bytes(),
+            image_size_bytes: self.image_size_bytes.swap_bytes(),
+            stored_crc_address: self.stored_crc_address.swap_bytes(),
+            execution_copy_size_bytes: self.execution_copy_size_bytes.swap_bytes(),
+            execution_address: self.execution_address.swap_bytes(),
+            boot_attempts: self.boot_attempts.swap_bytes(),
+            // bytes, so the same in either byte order
+            name: self.name,
+            integrity_type: self.integrity_type.swap_bytes(),
+            descriptor_crc: self.descriptor_crc.swap_bytes(),
+        }
+    }
+
+    /// This descriptor with every field in little endian byte order
+    const fn to_le(self) -> AppImageDescriptor {
+        if cfg!(target_endian = "big") {
+            self.byte_swapped()
+        } else {
+            self
+        }
+    }
+
+    /// Compute the CRC32 checksum of this structures current contents, as encoded on flash (little endian)
+    pub const fn compute_crc(&self) -> u32 {
+        self.compute_crc_with(&DESCRIPTOR_CRC_ALGORITHM)
+    }
+
+    /// Return the checksum under algorithm over the current contents of this descriptor, as encoded on flash (little endian).
+    /// Useful for matching the CRC unit of a chip whose ROM doesn't implement CRC_32_ISO_HDLC.
+    pub const fn compute_crc_with(&self, algorithm: &'static Algorithm<u32>) -> u32 {
+        checksum_without_crc(bytes_of(&self.to_le()), algorithm)
+    }
+
+    /// Fingerprint of this descriptor's contents for dedup and diffing in tooling: a CRC32 over the on-flash encoding of
+    /// every field but descriptor_version and descriptor_crc, so it stays the same across descriptor format versions.
+    /// This is not the descriptor_crc stored on flash and can't be used to validate a descriptor.
+    pub const fn content_crc(&self) -> u32 {
+        let le = self.to_le();
+        let (_, after_version) = bytes_of(&le).split_at(size_of::<u32>());
+        checksum_without_crc(after_version, &DESCRIPTOR_CRC_ALGORITHM)
+    }
+
+    /// Byte range (start, len) within the on-flash descriptor covered by descriptor_crc: everything but the trailing
+    /// descriptor_crc. Useful for feeding exactly the protected bytes into an external hash engine for measured boot.
+    pub const fn crc_covered_range() -> (usize, usize) {
+        (0, APP_IMAGE_DESCRIPTOR_SIZE - size_of::<u32>())
+    }
+
+    /// The descriptor_crc stored in this descriptor, copied out of the packed struct so callers never take a reference to it
+    pub const fn stored_crc(&self) -> u32 {
+        self.descriptor_crc
+    }
+
+    /// The image name up to its first zero byte. A name that isn't valid UTF-8 is cut short at the first invalid byte
+    /// rather than rejected, as it's only for logs.
+    pub fn name_str(&self) -> &str {
+        let name = &self.name;
+        let len = name.iter().position(|&byte| byte == 0).unwrap_or(name.len());
+        match core::str::from_utf8(&name[..len]) {
+            Ok(name) => name,
+            // can't fail as valid_up_to always ends on a character boundary
+            Err(err) => core::str::from_utf8(&name[..err.valid_up_to()]).unwrap(),
+        }
+    }
+
+    /// Check this structure's stored descriptor_crc against computed CRC32 checksum of its current contents
+    pub const fn is_crc_valid(&self) -> bool {
+        self.is_crc_valid_with(&DESCRIPTOR_CRC_ALGORITHM)
+    }
+
+    /// Check if the descriptor_crc value matches the current contents' checksum under algorithm
+    pub const fn is_crc_valid_with(&self, algorithm: &'static Algorithm<u32>) -> bool {
+        self.stored_crc() == self.compute_crc_with(algorithm)
+    }
+}
+
+/// A descriptor whose last field is a CRC32 checksum over every preceding byte, as encoded on flash (little endian).
+/// Implemented by every descriptor type so the checksum convention is defined once.
+pub trait CrcChecked: Pod {
+    /// Size of the descriptor in bytes, including the trailing CRC
+    const SIZE: usize;
+
+    /// This descriptor with every field in little endian byte order. Its own inverse, so it also decodes.
+    fn to_le(self) -> Self;
+
+    /// The stored trailing CRC32 checksum
+    fn stored_crc(&self) -> u32 {
+        let (_, crc) = bytemuck::bytes_of(self).split_at(Self::SIZE - size_of::<u32>());
+        u32::from_ne_bytes([crc[0], crc[1], crc[2], crc[3]])
+    }
+
+    /// Return the checksum under algorithm over the current contents, as encoded on flash (little endian)
+    fn compute_crc_with(&self, algorithm: &'static Algorithm<u32>) -> u32 {
+        checksum_without_crc(bytemuck::bytes_of(&self.to_le()), algorithm)
+    }
+
+    /// Return the CRC32 checksum over the current contents, as encoded on flash (little endian)
+    fn compute_crc(&self) -> u32 {
+        self.compute_crc_with(&DESCRIPTOR_CRC_ALGORITHM)
+    }
+
+    /// Check if the stored CRC matches the current contents' checksum under algorithm
+    fn is_crc_valid_with(&self, algorithm: &'static Algorithm<u32>) -> bool {
+        self.stored_crc() == self.compute_crc_with(algorithm)
+    }
+
+    /// Check if the stored CRC matches the current computed CRC32 checksum
+    fn is_crc_valid(&self) -> bool {
+        self.is_crc_valid_with(&DESCRIPTOR_CRC_ALGORITHM)
+    }
+}
+
+impl CrcChecked for BootableRegionDescriptorHeader {
+    const SIZE: usize = BOOT_REGION_DESCRIPTOR_SIZE;
+
+    fn to_le(self) -> Self {
+        BootableRegionDescriptorHeader::to_le(self)
+    }
+}
+
+impl CrcChecked for AppImageDescriptor {
+    const SIZE: usize = APP_IMAGE_DESCRIPTOR_SIZE;
+
+    fn to_le(self) -> Self {
+        AppImageDescriptor::to_le(self)
+    }
+}
+
+/// Fields and checks common to every descriptor region header width, so locating the active app descriptor can be generic
+/// over BootableRegionDescriptorHeader and BootableRegionDescriptorHeader64. Addresses are widened to u64. Fields only the
+/// 32-bit header carries, such as reserved_bytes and sequence, are not reachable through this trait.
+pub trait DescriptorHeader: CrcChecked {
+    /// Magic number identifying this header width
+    const SIGNATURE: u32;
+
+    /// The app image descriptor type this header describes
+    type App: ImageDescriptor;
+
+    /// Readable address where the app descriptor array is placed
+    fn app_descriptor_base_address(&self) -> u64;
+
+    /// The number of app descriptors in the bootable descriptor region
+    fn num_app_slots(&self) -> u32;
+
+    /// Which app descriptor should be booted
+    fn active_app_slot(&self) -> u32;
+}
+
+/// Fields and checks common to every app image descriptor width, so validating and copying an image can be generic over
+/// AppImageDescriptor and AppImageDescriptor64. Addresses and sizes are widened to u64, range checks still honor the
+/// descriptor's own width. Fields only the 32-bit descriptor carries, such as boot_attempts, name and integrity_type, are
+/// not reachable through this trait.
+pub trait ImageDescriptor: CrcChecked {
+    /// Largest address representable by this descriptor's address fields
+    const ADDRESS_MAX: u64;
+
+    /// Which slot this descriptor declares itself to be
+    fn app_slot_number(&self) -> u32;
+
+    /// Application firmware version
+    fn app_version(&self) -> u32;
+
+    /// Security version for roll-back protection
+    fn security_version(&self) -> u32;
+
+    /// App image behavior flags
+    fn flags(&self) -> u32;
+
+    /// Where the full, contiguous app image is stored
+    fn stored_address(&self) -> u64;
+
+    /// The size of the app image stored at stored_address
+    fn image_size_bytes(&self) -> u64;
+
+    /// The address where the image CRC32 checksum is kept
+    fn stored_crc_address(&self) -> u64;
+
+    /// How much memory to move from stored_address to execution_address
+    fn execution_copy_size_bytes(&self) -> u64;
+
+    /// Where to begin execution
+    fn execution_address(&self) -> u64;
+
+    /// Check if every bit in flag is set in this descriptor's flags
+    fn has_flag(&self, flag: u32) -> bool {
+        self.flags() & flag == flag
+    }
+
+    /// Check if APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS is set
+    fn is_copy_to_execution(&self) -> bool {
+        self.has_flag(APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS)
+    }
+
+    /// Check if APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK is set
+    fn is_skip_crc_check(&self) -> bool {
+        self.has_flag(APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK)
+    }
+
+    /// Bits set in flags that don't correspond to any known APP_IMAGE_FLAG_* constant
+    fn unknown_flags(&self) -> u32 {
+        self.flags() & !APP_IMAGE_FLAG_ALL
+    }
+
+    /// One past the last byte of the stored image, or None if it would pass ADDRESS_MAX
+    fn stored_end(&self) -> Option<u64> {
+        self.stored_address()
+            .checked_add(self.image_size_bytes())
+            .filter(|&end| end <= Self::ADDRESS_MAX)
+    }
+
+    /// One past the last byte copied to execution_address, or None if it would pass ADDRESS_MAX
+    fn execution_end(&self) -> Option<u64> {
+        self.execution_address()
+            .checked_add(self.execution_copy_size_bytes())
+            .filter(|&end| end <= Self::ADDRESS_MAX)
+    }
+
+    /// Check that neither the stored nor execution ranges wrap past the end of the address space
+    fn validate_ranges(&self) -> Result<(), ParseError> {
+        if self.stored_end().is_none() || self.execution_end().is_none() {
+            Err(ParseError::AddressOverflow {
+                slot: self.app_slot_number(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl DescriptorHeader for BootableRegionDescriptorHeader {
+    const SIGNATURE: u32 = BOOT_REGION_DESCRIPTOR_SIGNATURE;
+
+    type App = AppImageDescriptor;
+
+    fn app_descriptor_base_address(&self) -> u64 {
+        self.app_descriptor_base_address as u64
+    }
+
+    fn num_app_slots(&self) -> u32 {
+        self.num_app_slots
+    }
+
+    fn active_app_slot(&self) -> u32 {
+        self.active_app_slot
+    }
+}
+
+impl ImageDescriptor for AppImageDescriptor {
+    const ADDRESS_MAX: u64 = u32::MAX as u64;
+
+    fn app_slot_number(&self) -> u32 {
+        self.app_slot_number
+    }
+
+    fn app_version(&self) -> u32 {
+        self.app_version
+    }
+
+    fn security_version(&self) -> u32 {
+        self.security_version
+    }
+
+    fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    fn stored_address(&self) -> u64 {
+        self.stored_address as u64
+    }
+
+    fn image_size_bytes(&self) -> u64 {
+        self.image_size_bytes as u64
+    }
+
+    fn stored_crc_address(&self) -> u64 {
+        self.stored_crc_address as u64
+    }
+
+    fn execution_copy_size_bytes(&self) -> u64 {
+        self.execution_copy_size_bytes as u64
+    }
+
+    fn execution_address(&self) -> u64 {
+        self.execution_address as u64
+    }
+}
+
+/// Naturally aligned mirror of BootableRegionDescriptorHeader whose fields can be borrowed freely, for host tooling.
+/// BootableRegionDescriptorHeader remains the on-flash format, convert with From in either direction.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeaderView {
+    /// BOOT_REGION_DESCRIPTOR_SIGNATURE
+    pub signature: u32,
+
+    /// DESCRIPTOR_VERSION in the format h'MM_mmmm_pp
+    pub descriptor_version: u32,
+
+    /// BOOT_REGION_DESCRIPTOR_SIZE
+    pub descriptor_header_size_bytes: u32,
+
+    /// APP_IMAGE_DESCRIPTOR_SIZE
+    pub app_descriptor_size_bytes: u32,
+
+    /// Readable address where AppImageDescriptor\[num_active_slots\] is placed
+    pub app_descriptor_base_address: u32,
+
+    /// The number of AppImageDescriptor's in the bootable descriptor region
+    pub num_app_slots: u32,
+
+    /// Corresponds to which AppImageDescriptor should be booted
+    pub active_app_slot: u32,
+
+    /// Bytes reserved after the last AppImageDescriptor for future per-slot metadata. The bootloader never reads them, it
+    /// only needs to skip them when computing where the descriptor region ends.
+    pub reserved_bytes: u32,
+
+    /// Incremented on every rewrite of the header, see BootableRegionDescriptorHeader::sequence
+    pub sequence: u32,
+
+    /// CRC32 checksum of above parameters
+    pub header_crc: u32,
+}
+
+/// Naturally aligned mirror of AppImageDescriptor whose fields can be borrowed freely, for host tooling.
+/// AppImageDescriptor remains the on-flash format, convert with From in either direction.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AppView {
+    /// DESCRIPTOR_VERSION in the format h'MM_mmmm_pp
+    pub descriptor_version: u32,
+
+    /// Corresponds to index in AppImageDescriptor\[BootableRegionDescriptorHeader::num_app_slots\]
+    pub app_slot_number: u32,
+
+    /// Application version for handling recovery and roll forward or back behaviors
+    pub app_version: u32,
+
+    /// Security version corresponding to this application image for roll-back attack protection enablement
+    pub security_version: u32,
+
+    /// App image behavior flags
+    pub flags: u32,
+
+    /// Where the full, contiguous app image is stored
+    pub stored_address: u32,
+
+    /// The size of the app image stored at stored_address
+    pub image_size_bytes: u32,
+
+    /// The address where the CRC32 checksum over stored_address through stored_address + image_size_bytes is kept
+    pub stored_crc_address: u32,
+
+    /// how much memory to move from stored_address to execution_address before performing app load from bootloader
+    pub execution_copy_size_bytes: u32,
+
+    /// where to begin execution once the app image is validated and loaded
+    pub execution_address: u32,
+
+    /// how many times the bootloader has tried to boot this image without the app confirming a successful boot
+    pub boot_attempts: u32,
+
+    /// short human readable name or tag for the image, UTF-8 padded with zero bytes, see name_str
+    pub name: [u8; APP_IMAGE_NAME_SIZE],
+
+    /// how the image at stored_address is checked, one of the INTEGRITY_* constants
+    pub integrity_type: u32,
+
+    /// CRC32 checksum over the above parameters
+    pub descriptor_crc: u32,
+}
+
+impl From<BootableRegionDescriptorHeader> for HeaderView {
+    fn from(value: BootableRegionDescriptorHeader) -> Self {
+        HeaderView {
+            signature: value.signature,
+            descriptor_version: value.descriptor_version,
+            descriptor_header_size_bytes: value.descriptor_header_size_bytes,
+            app_descriptor_size_bytes: value.app_descriptor_size_bytes,
+            app_descriptor_base_address: value.app_descriptor_base_address,
+            num_app_slots: value.num_app_slots,
+            active_app_slot: value.active_app_slot,
+            reserved_bytes: value.reserved_bytes,
+            sequence: value.sequence,
+            header_crc: value.header_crc,
+        }
+    }
+}
+
+impl From<HeaderView> for BootableRegionDescriptorHeader {
+    fn from(value: HeaderView) -> Self {
+        BootableRegionDescriptorHeader {
+            signature: value.signature,
+            descriptor_version: value.descriptor_version,
+            descriptor_header_size_bytes: value.descriptor_header_size_bytes,
+            app_descriptor_size_bytes: value.app_descriptor_size_bytes,
+            app_descriptor_base_address: value.app_descriptor_base_address,
+            num_app_slots: value.num_app_slots,
+            active_app_slot: value.active_app_slot,
+            reserved_bytes: value.reserved_bytes,
+            sequence: value.sequence,
+            header_crc: value.header_crc,
+        }
+    }
+}
+
+impl From<AppImageDescriptor> for AppView {
+    fn from(value: AppImageDescriptor) -> Self {
+        AppView {
+            descriptor_version: value.descriptor_version,
+            app_slot_number: value.app_slot_number,
+            app_version: value.app_version,
+            security_version: value.security_version,
+            flags: value.flags,
+            stored_address: value.stored_address,
+            image_size_bytes: value.image_size_bytes,
+            stored_crc_address: value.stored_crc_address,
+            execution_copy_size_bytes: value.execution_copy_size_bytes,
+            execution_address: value.execution_address,
+            boot_attempts: value.boot_attempts,
+            name: value.name,
+            integrity_type: value.integrity_type,
+            descriptor_crc: value.descriptor_crc,
+        }
+    }
+}
+
+impl From<AppView> for AppImageDescriptor {
+    fn from(value: AppView) -> Self {
+        AppImageDescriptor {
+            descriptor_version: value.descriptor_version,
+            app_slot_number: value.app_slot_number,
+            app_version: value.app_version,
+            security_version: value.security_version,
+            flags: value.flags,
+            stored_address: value.stored_address,
+            image_size_bytes: value.image_size_bytes,
+            stored_crc_address: value.stored_crc_address,
+            execution_copy_size_bytes: value.execution_copy_size_bytes,
+            execution_address: value.execution_address,
+            boot_attempts: value.boot_attempts,
+            name: value.name,
+            integrity_type: value.integrity_type,
+            descriptor_crc: value.descriptor_crc,
+        }
+    }
+}
+
+impl PartialEq for BootableRegionDescriptorHeader {
+    /// Byte-wise comparison of every field, including header_crc
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for BootableRegionDescriptorHeader {}
+
+impl PartialEq for AppImageDescriptor {
+    /// Byte-wise comparison of every field, including descriptor_crc
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl Eq for AppImageDescriptor {}
+
+impl core::hash::Hash for BootableRegionDescriptorHeader {
+    /// Hashes the same bytes PartialEq compares
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl core::hash::Hash for AppImageDescriptor {
+    /// Hashes the same bytes PartialEq compares
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl Default for AppImageDescriptor {
+    /// An all-zero app image descriptor with a valid descriptor_crc, computed with CRC_32_ISO_HDLC
+    fn default() -> Self {
+        let mut app_image_descriptor: AppImageDescriptor = bytemuck::Zeroable::zeroed();
+        app_image_descriptor.descriptor_crc = app_image_descriptor.compute_crc();
+
+        app_image_descriptor
+    }
+}
+
+impl TryFrom<&[u8]> for BootableRegionDescriptorHeader {
+    type Error = ParseError;
+
+    /// Parse and validate a header in the on-flash format, see BootableRegionDescriptorHeader::from_bytes
+    fn try_from(buf: &[u8]) -> Result<Self, Self::Error> {
+        BootableRegionDescriptorHeader::from_bytes(buf)
+    }
+}
+
+impl TryFrom<&[u8]> for AppImageDescriptor {
+    type Error = ParseError;
+
+    /// Parse and validate an app image descriptor in the on-flash format, see AppImageDescriptor::from_bytes
+    fn try_from(buf: &[u8]) -> Result<Self, Self::Error> {
+        AppImageDescriptor::from_bytes(buf)
+    }
+}
+
+/// Builder for AppImageDescriptor, an alternative to the positional argument const constructors
+#[derive(Copy, Clone, Debug, Default)]
+pub struct AppImageDescriptorBuilder {
+    slot: u32,
+    app_version: u32,
+    security_version: u32,
+    flags: u32,
+    stored_address: u32,
+    image_size_bytes: u32,
+    stored_crc_address: u32,
+    execution: Option<(u32, u32)>,
+    name: [u8; APP_IMAGE_NAME_SIZE],
+    integrity_type: u32,
+}
+
+impl AppImageDescriptorBuilder {
+    /// Start building an app image descriptor with every field zeroed
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set app_slot_number
+    pub fn slot(mut self, slot: u32) -> Self {
+        self.slot = slot;
+        self
+    }
+
+    /// Set app_version
+    pub fn app_version(mut self, app_version: u32) -> Self {
+        self.app_version = app_version;
+        self
+    }
+
+    /// Set security_version
+    pub fn security_version(mut self, security_version: u32) -> Self {
+        self.security_version = security_version;
+        self
+    }
+
+    /// Set app image flags, either raw APP_IMAGE_FLAG_* bits or AppImageFlags. APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS is
+    /// added automatically if execution is set
+    pub fn flags(mut self, flags: impl Into<u32>) -> Self {
+        self.flags = flags.into();
+        self
+    }
+
+    /// Set name, truncated to APP_IMAGE_NAME_SIZE bytes without splitting a character
+    pub fn name(mut self, name: &str) -> Self {
+        let mut len = name.len().min(APP_IMAGE_NAME_SIZE);
+        while !name.is_char_boundary(len) {
+            len -= 1;
+        }
+
+        self.name = [0; APP_IMAGE_NAME_SIZE];
+        self.name[..len].copy_from_slice(&name.as_bytes()[..len]);
+        self
+    }
+
+    /// Set integrity_type, one of the INTEGRITY_* constants
+    pub fn integrity_type(mut self, integrity_type: u32) -> Self {
+        self.integrity_type = integrity_type;
+        self
+    }
+
+    /// Set where the app image is stored and its size
+    pub fn stored(mut self, stored_address: u32, image_size_bytes: u32) -> Self {
+        self.stored_address = stored_address;
+        self.image_size_bytes = image_size_bytes;
+        self
+    }
+
+    /// Set where the app image CRC32 checksum is stored
+    pub fn stored_crc_address(mut self, stored_crc_address: u32) -> Self {
+        self.stored_crc_address = stored_crc_address;
+        self
+    }
+
+    /// Copy execution_copy_size_bytes of the image to execution_address before executing. If never set, the image executes in place
+    pub fn execution(mut self, execution_address: u32, execution_copy_size_bytes: u32) -> Self {
+        self.execution = Some((execution_address, execution_copy_size_bytes));
+        self
+    }
+
+    /// Generate the app image descriptor, filling in descriptor_version and descriptor_crc
+    pub fn build(self) -> AppImageDescriptor {
+        let (flags, execution_address, execution_copy_size_bytes) = match self.execution {
+            Some((address, size)) => (self.flags | APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS, address, size),
+            None => (self.flags, self.stored_address, 0),
+        };
+
+        let mut app_image_descriptor = AppImageDescriptor {
+            descriptor_version: DESCRIPTOR_VERSION,
+            app_slot_number: self.slot,
+            app_version: self.app_version,
+            security_version: self.security_version,
+            flags,
+            stored_address: self.stored_address,
+            image_size_bytes: self.image_size_bytes,
+            stored_crc_address: self.stored_crc_address,
+            execution_copy_size_bytes,
+            execution_address,
+            boot_attempts: 0,
+            name: self.name,
+            integrity_type: self.integrity_type,
+            descriptor_crc: 0,
+        };
+
+        app_image_descriptor.descriptor_crc = app_image_descriptor.compute_crc();
+
+        app_image_descriptor
+    }
+}
+
+/// Parse the header at the start of region and return it along with a zero-copy view of every app descriptor, after
+/// validating each as BootableRegionDescriptors::from_bytes does. app_descriptor_base_address is treated as a byte offset
+/// within region. The view reinterprets the on-flash (little endian) bytes in place, so this is only available on little
+/// endian hosts; elsewhere use BootableRegionDescriptors::from_bytes, which decodes a copy of each descriptor.
+#[cfg(target_endian = "little")]
+pub fn parse_descriptors(region: &[u8]) -> Result<(BootableRegionDescriptorHeader, &[AppImageDescriptor]), ParseError> {
+    let header = BootableRegionDescriptorHeader::from_bytes(region)?;
+    header.validate_descriptor_base(0)?;
+
+    let offset = header.app_descriptor_base_address as usize;
+    let end = (header.num_app_slots as usize)
+        .checked_mul(APP_IMAGE_DESCRIPTOR_SIZE)
+        .and_then(|size| size.checked_add(offset))
+        .ok_or(ParseError::ArithmeticOverflow)?;
+    let bytes = region.get(offset..end).ok_or(ParseError::BufferTooSmall {
+        needed: end,
+        got: region.len(),
+    })?;
+
+    for (slot, app) in (0..).zip(bytes.chunks_exact(APP_IMAGE_DESCRIPTOR_SIZE)) {
+        AppImageDescriptor::from_encoded_bytes(app, false, Some(slot))?;
+    }
+
+    // can't fail as AppImageDescriptor is packed so any alignment is acceptable and the length is a whole number of descriptors
+    Ok((header, bytemuck::try_cast_slice(bytes).unwrap()))
+}
+
+/// Recompute and rewrite header_crc and every descriptor_crc in a region laid out as for BootableRegionDescriptors::from_bytes,
+/// leaving all other fields untouched. Useful for build tools after editing fields in place. The header signature must be
+/// present, but no CRC needs to be valid beforehand.
+pub fn recompute_all_crcs(region: &mut [u8]) -> Result<(), ParseError> {
+    rewrite_region(region, |header| Ok(header.app_descriptor_base_address as usize))
+}
+
+/// Recompute and rewrite only header_crc of the header at the start of region, for tools that edit header fields such as
+/// active_app_slot or num_app_slots in place. Only the header bytes are touched, so region need not hold the app
+/// descriptors. The header signature must be present, but header_crc needn't be valid beforehand.
+pub fn recompute_header_crc(region: &mut [u8]) -> Result<(), ParseError> {
+    let mut header = BootableRegionDescriptorHeader::read_unchecked(region)?;
+    if header.signature != BOOT_REGION_DESCRIPTOR_SIGNATURE {
+        return Err(ParseError::InvalidSignature);
+    }
+
+    header.recompute_crc();
+    header.write_to(region)?;
+
+    Ok(())
+}
+
+/// Decode the header at the start of region, let edit adjust it and return the region offset of the app descriptors, then
+/// write back the header and every app descriptor with recomputed CRCs
+fn rewrite_region(
+    region: &mut [u8],
+    edit: impl FnOnce(&mut BootableRegionDescriptorHeader) -> Result<usize, ParseError>,
+) -> Result<(), ParseError> {
+    let got = region.len();
+    let mut header = BootableRegionDescriptorHeader::read_unchecked(region)?;
+    if header.signature != BOOT_REGION_DESCRIPTOR_SIGNATURE {
+        return Err(ParseError::InvalidSignature);
+    }
+
+    // make sure every app descriptor lies within region before touching any of them
+    let offset = edit(&mut header)?;
+    let needed = (header.num_app_slots as usize)
+        .checked_mul(APP_IMAGE_DESCRIPTOR_SIZE)
+        .and_then(|size| size.checked_add(offset))
+        .ok_or(ParseError::ArithmeticOverflow)?;
+    if got < needed {
+        return Err(ParseError::BufferTooSmall { needed, got });
+    }
+
+    header.header_crc = header.compute_crc();
+    header.write_to(region)?;
+
+    for chunk in region[offset..needed].chunks_exact_mut(APP_IMAGE_DESCRIPTOR_SIZE) {
+        let mut app = bytemuck::pod_read_unaligned::<AppImageDescriptor>(chunk).to_le();
+        app.recompute_crc();
+        app.write_to(chunk)?;
+    }
+
+    Ok(())
+}
+
+/// Assemble header followed by apps into one contiguous region in the on-flash (little endian) format, usable to place a
+/// fully initialized descriptor region in a static:
+///
+/// ```
+/// # use ec_slimloader_descriptors::*;
+/// const SLOTS: usize = 2;
+/// const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + SLOTS * APP_IMAGE_DESCRIPTOR_SIZE;
+/// static REGION: [u8; N] = build_region(
+///     BootableRegionDescriptorHeader::new(SLOTS as u32, 0, 0x1000_0000 + BOOT_REGION_DESCRIPTOR_SIZE as u32),
+///     &[
+///         AppImageDescriptor::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0x1001_0000, 0x8000, 0x1001_8000),
+///         AppImageDescriptor::new_execute_in_place_image(1, 1, 0, APP_IMAGE_FLAG_NONE, 0x1002_0000, 0x8000, 0x1002_8000),
+///     ],
+/// );
+/// ```
+///
+/// The apps are placed immediately after the header, so header.app_descriptor_base_address should be the address the
+/// region is linked at plus BOOT_REGION_DESCRIPTOR_SIZE. N must be at least
+/// BOOT_REGION_DESCRIPTOR_SIZE + apps.len() * APP_IMAGE_DESCRIPTOR_SIZE, any bytes past that are zero. Panics, at compile
+/// time when evaluated in a const or static, if N is too small.
+pub const fn build_region<const N: usize>(
+    header: BootableRegionDescriptorHeader,
+    apps: &[AppImageDescriptor],
+) -> [u8; N] {
+    assert!(
+        N >= BOOT_REGION_DESCRIPTOR_SIZE + apps.len() * APP_IMAGE_DESCRIPTOR_SIZE,
+        "region too small for header and app descriptors"
+    );
+
+    let mut region = [0u8; N];
+
+    let le = header.to_le();
+    let bytes = bytes_of(&le);
+    let mut i = 0;
+    while i < BOOT_REGION_DESCRIPTOR_SIZE {
+        region[i] = bytes[i];
+        i += 1;
+    }
+
+    let mut slot = 0;
+    while slot < apps.len() {
+        let le = apps[slot].to_le();
+        let bytes = bytes_of(&le);
+        let offset = BOOT_REGION_DESCRIPTOR_SIZE + slot * APP_IMAGE_DESCRIPTOR_SIZE;
+        let mut i = 0;
+        while i < APP_IMAGE_DESCRIPTOR_SIZE {
+            region[offset + i] = bytes[i];
+            i += 1;
+        }
+        slot += 1;
+    }
+
+    region
+}
+
+/// Check the parts of a header that can be wrong in a const built region: signature, at least one slot, and the active
+/// slot in range. Assert on it next to the const so layout mistakes fail the build instead of the first boot:
+///
+/// ```
+/// # use ec_slimloader_descriptors::*;
+/// const HEADER: BootableRegionDescriptorHeader = BootableRegionDescriptorHeader::new(2, 1, 0x1000_0020);
+/// const _: () = assert!(validate_header_const(&HEADER));
+/// ```
+///
+/// ```compile_fail
+/// # use ec_slimloader_descriptors::*;
+/// // active slot 2 doesn't exist in a 2 slot region
+/// const HEADER: BootableRegionDescriptorHeader = BootableRegionDescriptorHeader::new(2, 2, 0x1000_0020);
+/// const _: () = assert!(validate_header_const(&HEADER));
+/// ```
+pub const fn validate_header_const(h: &BootableRegionDescriptorHeader) -> bool {
+    h.signature == BOOT_REGION_DESCRIPTOR_SIGNATURE && h.num_app_slots >= 1 && h.active_app_slot < h.num_app_slots
+}
+
+/// CRC over bytes excluding the trailing u32 CRC field, the convention shared by every descriptor type
+const fn checksum_without_crc(bytes: &[u8], algorithm: &'static Algorithm<u32>) -> u32 {
+    let (without_crc, _) = bytes.split_at(bytes.len() - size_of::<u32>());
+    Crc::<u32>::new(algorithm).checksum(without_crc)
+}
+
+/// Check if bytes were never written: all 0xFF as erased NOR flash reads, or all 0x00 as a zeroed buffer does
+fn is_erased(bytes: &[u8]) -> bool {
+    bytes.iter().all(|&byte| byte == 0xFF) || bytes.iter().all(|&byte| byte == 0x00)
+}
+
+/// Copy bytes into the start of buf, returning the number of bytes written
+fn write_bytes(bytes: &[u8], buf: &mut [u8]) -> Result<usize, ParseError> {
+    let got = buf.len();
+    buf.get_mut(..bytes.len())
+        .ok_or(ParseError::BufferTooSmall {
+            needed: bytes.len(),
+            got,
+        })?
+        .copy_from_slice(bytes);
+
+    Ok(bytes.len())
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    /// Build N execute in place app descriptors, each 1MB apart in flash with the image CRC placed right after the image
+    fn xip_apps<const N: usize>() -> [AppImageDescriptor; N] {
+        core::array::from_fn(|i| {
+            let slot = i as u32;
+            let stored_address = 0x1000_0000 + slot * 0x10_0000;
+            AppImageDescriptor::new_execute_in_place_image(
+                slot,
+                slot + 1,
+                0,
+                APP_IMAGE_FLAG_NONE,
+                stored_address,
+                0x1000,
+                stored_address + 0x1000,
+            )
+        })
+    }
+
+    /// Lay out a header followed immediately by apps, with app_descriptor_base_address as the offset into the buffer
+    fn region_bytes<const N: usize>(active_app_slot: u32, apps: &[AppImageDescriptor]) -> [u8; N] {
+        let header =
+            BootableRegionDescriptorHeader::new(apps.len() as u32, active_app_slot, BOOT_REGION_DESCRIPTOR_SIZE as u32);
+
+        let mut region = [0u8; N];
+        let mut offset = header.write_to(&mut region).unwrap();
+        for app in apps {
+            offset += app.write_to(&mut region[offset..]).unwrap();
+        }
+
+        region
+    }
+
+    /// Word aligned storage, as descriptors on flash always are, for handing host memory to the pointer constructors
+    #[repr(C, align(4))]
+    struct Aligned<T: ?Sized>(T);
+
+    impl<T: ?Sized> Aligned<T> {
+        fn ptr(&self) -> *const u32 {
+            &self.0 as *const T as *const u32
+        }
+    }
+
+    impl<T: ?Sized> core::ops::Deref for Aligned<T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.0
+        }
+    }
+
+    impl<T: ?Sized> core::ops::DerefMut for Aligned<T> {
+        fn deref_mut(&mut self) -> &mut T {
+            &mut self.0
+        }
+    }
+
+    /// Construct a descriptor manager over a header and word aligned app descriptor array held in host memory
+    fn region_from_parts<'a>(
+        header: &'a BootableRegionDescriptorHeader,
+        apps: &'a Aligned<[AppImageDescriptor]>,
+    ) -> BootableRegionDescriptors<'a> {
+        // the header is copied out during construction, so only the apps need to outlive the result
+        BootableRegionDescriptors::from_header_and_app_regions(Aligned(*header).ptr(), apps.ptr()).unwrap()
+    }
+
+    #[test]
+    fn test_ram_descriptor_gen() {
+        let app_image_descriptor = AppImageDescriptor::new_ram_image(
+            0,
+            0,
+            0,
+            APP_IMAGE_FLAG_NONE | APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+            0,
+            0,
+            0,
+            0,
+        );
+
+        assert_ne!(app_image_descriptor.flags & APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS, 0);
+        let embedded_crc = app_image_descriptor.descriptor_crc;
+        let computed_crc = app_image_descriptor.compute_crc();
+        assert_eq!(embedded_crc, computed_crc);
+    }
+
+    #[test]
+    fn header_from_bytes_unaligned() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000);
+
+        // place the header one byte into a word aligned buffer so the slice is guaranteed to be misaligned for a u32 load
+        #[repr(align(4))]
+        struct Aligned([u8; BOOT_REGION_DESCRIPTOR_SIZE + 1]);
+        let mut buf = Aligned([0u8; BOOT_REGION_DESCRIPTOR_SIZE + 1]);
+        buf.0[1..].copy_from_slice(header.as_bytes());
+
+        let parsed = BootableRegionDescriptorHeader::from_bytes(&buf.0[1..]).unwrap();
+        assert_eq!(parsed.as_bytes(), header.as_bytes());
+    }
+
+    #[test]
+    fn header_from_bytes_too_small() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000);
+
+        let result = BootableRegionDescriptorHeader::from_bytes(&header.as_bytes()[..BOOT_REGION_DESCRIPTOR_SIZE - 1]);
+        assert!(matches!(
+            result,
+            Err(ParseError::BufferTooSmall {
+                needed: BOOT_REGION_DESCRIPTOR_SIZE,
+                got
+            }) if got == BOOT_REGION_DESCRIPTOR_SIZE - 1
+        ));
+    }
+
+    #[test]
+    fn header_set_active_slot() {
+        let mut header = BootableRegionDescriptorHeader::new(2, 0, 0x1000);
+
+        header.set_active_slot(1).unwrap();
+        assert_eq!({ header.active_app_slot }, 1);
+        assert!(header.is_crc_valid());
+
+        header.set_active_slot(0).unwrap();
+        assert_eq!({ header.active_app_slot }, 0);
+        assert!(header.is_crc_valid());
+
+        let before = header;
+        assert!(matches!(header.set_active_slot(2), Err(ParseError::InvalidAppSlot)));
+        assert_eq!(header.as_bytes(), before.as_bytes());
+    }
+
+    #[test]
+    fn bootable_region_descriptors_iter() {
+        let header = BootableRegionDescriptorHeader::new(3, 1, 0);
+        let apps = Aligned(xip_apps::<3>());
+        let descriptors = region_from_parts(&header, &apps);
+
+        let mut count = 0;
+        for (i, app) in descriptors.iter().enumerate() {
+            assert_eq!(app.unwrap().as_bytes(), apps[i].as_bytes());
+            count += 1;
+        }
+        assert_eq!(count, 3);
+
+        let newest = descriptors
+            .iter()
+            .map(Result::unwrap)
+            .max_by_key(|app| app.app_version)
+            .unwrap();
+        assert_eq!({ newest.app_slot_number }, 2);
+    }
+
+    #[test]
+    fn parse_error_display() {
+        use core::fmt::Write;
+
+        let mut out = heapless::String::<64>::new();
+        write!(
+            out,
+            "{}",
+            ParseError::InvalidHeaderCrc {
+                found: 0x1234,
+                expected: 0xdead_beef
+            }
+        )
+        .unwrap();
+        assert_eq!(
+            out.as_str(),
+            "invalid header CRC: found 0x00001234, expected 0xdeadbeef"
+        );
+    }
+
+    #[test]
+    fn select_boot_slot_security_version() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0);
+        let apps = Aligned([
+            AppImageDescriptor::new_execute_in_place_image(
+                0,
+                1,
+                3,
+                APP_IMAGE_FLAG_NONE,
+                0x1000_0000,
+                0x1000,
+                0x1000_1000,
+            ),
+            AppImageDescriptor::new_execute_in_place_image(
+                1,
+                2,
+                5,
+                APP_IMAGE_FLAG_NONE,
+                0x1010_0000,
+                0x1000,
+                0x1010_1000,
+            ),
+        ]);
+        let descriptors = region_from_parts(&header, &apps);
+
+        // accept
+        let selected = descriptors.select_boot_slot(4).unwrap();
+        assert_eq!({ selected.app_slot_number }, 1);
+
+        // equal boundary
+        let selected = descriptors.select_boot_slot(5).unwrap();
+        assert_eq!({ selected.app_slot_number }, 1);
+
+        // reject
+        assert!(matches!(
+            descriptors.select_boot_slot(6),
+            Err(ParseError::SecurityVersionRollback { found: 5, minimum: 6 })
+        ));
+
+        // a recovery image is never selected for normal boot
+        let recovery = AppImageDescriptor::builder()
+            .slot(1)
+            .security_version(5)
+            .flags(APP_IMAGE_FLAG_RECOVERY)
+            .stored(0x1010_0000, 0x1000)
+            .build();
+        let apps = Aligned([apps[0], recovery]);
+        let descriptors = region_from_parts(&header, &apps);
+        assert!(matches!(
+            descriptors.select_boot_slot(0),
+            Err(ParseError::RecoveryActive { slot: 1 })
+        ));
+    }
+
+    #[test]
+    fn app_descriptor_flag_helpers() {
+        let ram = AppImageDescriptor::new_ram_image(
+            0,
+            0,
+            0,
+            APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+            0x1000_0000,
+            0x1000,
+            0x2000_0000,
+            0x1000_1000,
+        );
+        assert!(ram.is_copy_to_execution());
+        assert!(ram.is_skip_crc_check());
+        assert!(ram.has_flag(APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS | APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK));
+
+        let xip = AppImageDescriptor::new_execute_in_place_image(
+            0,
+            0,
+            0,
+            APP_IMAGE_FLAG_NONE,
+            0x1000_0000,
+            0x1000,
+            0x1000_1000,
+        );
+        assert!(!xip.is_copy_to_execution());
+        assert!(!xip.is_skip_crc_check());
+        assert!(xip.has_flag(APP_IMAGE_FLAG_NONE));
+
+        const XIP_SKIPS_CRC: bool = AppImageDescriptor::new_execute_in_place_image(
+            0,
+            0,
+            0,
+            APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+            0x1000_0000,
+            0x1000,
+            0x1000_1000,
+        )
+        .is_skip_crc_check();
+        const { assert!(XIP_SKIPS_CRC) };
+    }
+
+    #[test]
+    fn header_find_in() {
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0x1000);
+        let mut buf = [0xFFu8; 256];
+        buf[128..128 + BOOT_REGION_DESCRIPTOR_SIZE].copy_from_slice(header.as_bytes());
+
+        let (offset, found) = BootableRegionDescriptorHeader::find_in(&buf, 64).unwrap();
+        assert_eq!(offset, 128);
+        assert_eq!(found.as_bytes(), header.as_bytes());
+
+        // a header that isn't on an align boundary is not found
+        assert!(matches!(
+            BootableRegionDescriptorHeader::find_in(&buf, 48),
+            Err(ParseError::InvalidSignature)
+        ));
+
+        // the first aligned hit is validated
+        buf[128 + BOOT_REGION_DESCRIPTOR_SIZE - 1] ^= 0xFF;
+        assert!(matches!(
+            BootableRegionDescriptorHeader::find_in(&buf, 64),
+            Err(ParseError::InvalidHeaderCrc { .. })
+        ));
+    }
+
+    #[test]
+    fn descriptors_write_to() {
+        let header = BootableRegionDescriptorHeader::new(1, 0, BOOT_REGION_DESCRIPTOR_SIZE as u32);
+        let [app] = xip_apps::<1>();
+
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE + APP_IMAGE_DESCRIPTOR_SIZE];
+        let written = header.write_to(&mut buf).unwrap();
+        assert_eq!(written, BOOT_REGION_DESCRIPTOR_SIZE);
+        let written = app.write_to(&mut buf[written..]).unwrap();
+        assert_eq!(written, APP_IMAGE_DESCRIPTOR_SIZE);
+
+        assert_eq!(&buf[..BOOT_REGION_DESCRIPTOR_SIZE], header.as_bytes());
+        assert_eq!(&buf[BOOT_REGION_DESCRIPTOR_SIZE..], app.as_bytes());
+
+        assert!(matches!(
+            app.write_to(&mut buf[BOOT_REGION_DESCRIPTOR_SIZE + 1..]),
+            Err(ParseError::BufferTooSmall {
+                needed: APP_IMAGE_DESCRIPTOR_SIZE,
+                got
+            }) if got == APP_IMAGE_DESCRIPTOR_SIZE - 1
+        ));
+    }
+
+    #[test]
+    fn region_overlaps() {
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0);
+
+        // adjacent but not overlapping
+        let apps = Aligned([
+            AppImageDescriptor::new_execute_in_place_image(0, 0, 0, APP_IMAGE_FLAG_NONE, 0x1000, 0x1000, 0),
+            AppImageDescriptor::new_execute_in_place_image(1, 0, 0, APP_IMAGE_FLAG_NONE, 0x2000, 0x1000, 0),
+        ]);
+        assert!(region_from_parts(&header, &apps).check_region_overlaps().is_ok());
+
+        // one byte overlap
+        let apps = Aligned([
+            AppImageDescriptor::new_execute_in_place_image(0, 0, 0, APP_IMAGE_FLAG_NONE, 0x1000, 0x1001, 0),
+            AppImageDescriptor::new_execute_in_place_image(1, 0, 0, APP_IMAGE_FLAG_NONE, 0x2000, 0x1000, 0),
+        ]);
+        assert!(matches!(
+            region_from_parts(&header, &apps).check_region_overlaps(),
+            Err(ParseError::OverlappingRegions { slot_a: 0, slot_b: 1 })
+        ));
+    }
+
+    #[test]
+    fn app_descriptor_ranges() {
+        let xip = AppImageDescriptor::new_execute_in_place_image(0, 0, 0, APP_IMAGE_FLAG_NONE, 0x1000, 0x1000, 0);
+        assert_eq!(xip.stored_end(), Some(0x2000));
+        assert_eq!(xip.execution_end(), Some(0x1000));
+        assert!(xip.validate_ranges().is_ok());
+
+        let ram = AppImageDescriptor::new_ram_image(3, 0, 0, APP_IMAGE_FLAG_NONE, 0x1000, 0x1000, 0xFFFF_F800, 0);
+        assert_eq!(ram.stored_end(), Some(0x2000));
+        assert_eq!(ram.execution_end(), None);
+        assert!(matches!(
+            ram.validate_ranges(),
+            Err(ParseError::AddressOverflow { slot: 3 })
+        ));
+
+        // end of image wraps the address space, rejected while constructing the region
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0);
+        let apps = Aligned([
+            AppImageDescriptor::new_execute_in_place_image(0, 0, 0, APP_IMAGE_FLAG_NONE, 0x1000, 0x1000, 0),
+            AppImageDescriptor::new_execute_in_place_image(0, 0, 0, APP_IMAGE_FLAG_NONE, 0xFFFF_F000, 0x2000, 0),
+        ]);
+        assert!(matches!(
+            BootableRegionDescriptors::from_header_and_app_regions(Aligned(header).ptr(), apps.ptr()),
+            Err(ParseError::AddressOverflow { slot: 1 })
+        ));
+    }
+
+    #[test]
+    fn builder_matches_const_constructors() {
+        let built = AppImageDescriptor::builder()
+            .slot(1)
+            .app_version(2)
+            .security_version(3)
+            .flags(APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK)
+            .stored(0x1000_0000, 0x4000)
+            .stored_crc_address(0x1000_4000)
+            .build();
+        let xip = AppImageDescriptor::new_execute_in_place_image(
+            1,
+            2,
+            3,
+            APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+            0x1000_0000,
+            0x4000,
+            0x1000_4000,
+        );
+        assert_eq!(built.as_bytes(), xip.as_bytes());
+
+        let built = AppImageDescriptor::builder()
+            .slot(1)
+            .app_version(2)
+            .security_version(3)
+            .stored(0x1000_0000, 0x4000)
+            .stored_crc_address(0x1000_4000)
+            .execution(0x2000_0000, 0x4000)
+            .build();
+        let ram = AppImageDescriptor::new_ram_image(
+            1,
+            2,
+            3,
+            APP_IMAGE_FLAG_NONE,
+            0x1000_0000,
+            0x4000,
+            0x2000_0000,
+            0x1000_4000,
+        );
+        assert_eq!(built.as_bytes(), ram.as_bytes());
+    }
+
+    #[test]
+    fn verify_image_crc() {
+        let image = [0xA5u8; 64];
+        let crc = Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(&image);
+
+        let checked = AppImageDescriptor::new_execute_in_place_image(0, 0, 0, APP_IMAGE_FLAG_NONE, 0x1000, 64, 0x1040);
+        assert!(checked.verify_image_crc(&image, crc).is_ok());
+
+        // anything past image_size_bytes is not covered
+        let mut padded = [0xFFu8; 128];
+        padded[..64].copy_from_slice(&image);
+        assert!(checked.verify_image_crc(&padded, crc).is_ok());
+
+        assert!(matches!(
+            checked.verify_image_crc(&image, !crc),
+            Err(ParseError::InvalidImageCrc { found, expected }) if found == crc && expected == !crc
+        ));
+        assert!(matches!(
+            checked.verify_image_crc(&image[..63], crc),
+            Err(ParseError::BufferTooSmall { needed: 64, got: 63 })
+        ));
+
+        let skipped = AppImageDescriptor::new_execute_in_place_image(
+            0,
+            0,
+            0,
+            APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+            0x1000,
+            64,
+            0x1040,
+        );
+        assert!(skipped.verify_image_crc(&image, !crc).is_ok());
+    }
+
+    #[test]
+    fn image_digest_chunked() {
+        let mut image = [0u8; 768];
+        for (i, byte) in image.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let descriptor = AppImageDescriptor::new_execute_in_place_image(0, 0, 0, APP_IMAGE_FLAG_NONE, 0x1000, 768, 0);
+
+        let mut one_shot = descriptor.image_digest();
+        one_shot.update(&image);
+        let expected = one_shot.finalize();
+
+        let mut chunked = descriptor.image_digest();
+        for chunk in image.chunks(256) {
+            chunked.update(chunk);
+        }
+        assert!(descriptor.finish_image_digest(chunked, expected).is_ok());
+        assert!(descriptor.verify_image_crc(&image, expected).is_ok());
+
+        let mut chunked = descriptor.image_digest();
+        for chunk in image[..512].chunks(256) {
+            chunked.update(chunk);
+        }
+        assert!(matches!(
+            descriptor.finish_image_digest(chunked, expected),
+            Err(ParseError::InvalidImageCrc { .. })
+        ));
+    }
+
+    #[test]
+    fn image_segments() {
+        let mut image = [0u8; 300];
+        for (i, byte) in image.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let descriptor = AppImageDescriptor::new_execute_in_place_image(0, 0, 0, APP_IMAGE_FLAG_NONE, 0x1000, 300, 0);
+        let expected = Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(&image);
+        assert!(descriptor.verify_image_crc(&image, expected).is_ok());
+
+        let segments = [&image[..10], &image[10..200], &image[200..]];
+        assert!(descriptor.verify_image_segments(segments, expected).is_ok());
+
+        // bytes past image_size_bytes are ignored, as in verify_image_crc
+        let padded = [0xFFu8; 4];
+        assert!(descriptor
+            .verify_image_segments([&image[..150], &image[150..], &padded[..]], expected)
+            .is_ok());
+
+        assert!(matches!(
+            descriptor.verify_image_segments([&image[..10], &image[200..]], expected),
+            Err(ParseError::BufferTooSmall { needed: 300, got: 110 })
+        ));
+        assert!(matches!(
+            descriptor.verify_image_segments([&image[200..], &image[..200]], expected),
+            Err(ParseError::InvalidImageCrc { .. })
+        ));
+    }
+
+    #[test]
+    fn endianness_round_trip() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000_0020);
+        let app = AppImageDescriptor::new_ram_image(
+            1,
+            0x0102_0304,
+            2,
+            APP_IMAGE_FLAG_NONE,
+            0x1000_0000,
+            0x4000,
+            0x2000_0000,
+            0x1000_4000,
+        );
+
+        let mut le = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        let mut be = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        app.write_to_endian(&mut le, Endianness::Little).unwrap();
+        app.write_to_endian(&mut be, Endianness::Big).unwrap();
+
+        // field encoding is fixed regardless of host: app_slot_number is the second word
+        assert_eq!(le[4..8], [1, 0, 0, 0]);
+        assert_eq!(be[4..8], [0, 0, 0, 1]);
+
+        // big endian encoding is every little endian word reversed
+        for (le_word, be_word) in le.chunks(4).zip(be.chunks(4)) {
+            assert!(le_word.iter().eq(be_word.iter().rev()));
+        }
+
+        assert_eq!(
+            AppImageDescriptor::from_bytes_endian(&le, Endianness::Little)
+                .unwrap()
+                .as_bytes(),
+            app.as_bytes()
+        );
+        assert_eq!(
+            AppImageDescriptor::from_bytes_endian(&be, Endianness::Big)
+                .unwrap()
+                .as_bytes(),
+            app.as_bytes()
+        );
+        assert_eq!(AppImageDescriptor::from_bytes(&le).unwrap().as_bytes(), app.as_bytes());
+
+        // decoding with the wrong byte order fails the CRC check rather than misinterpreting fields
+        assert!(matches!(
+            AppImageDescriptor::from_bytes_endian(&le, Endianness::Big),
+            Err(ParseError::InvalidAppCrc { .. })
+        ));
+
+        let mut le = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        let mut be = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        header.write_to_endian(&mut le, Endianness::Little).unwrap();
+        header.write_to_endian(&mut be, Endianness::Big).unwrap();
+        assert_eq!(le[16..20], [0x20, 0x00, 0x00, 0x10]);
+        assert_eq!(be[16..20], [0x10, 0x00, 0x00, 0x20]);
+        assert_eq!(
+            BootableRegionDescriptorHeader::from_bytes_endian(&le, Endianness::Little)
+                .unwrap()
+                .as_bytes(),
+            header.as_bytes()
+        );
+        assert_eq!(
+            BootableRegionDescriptorHeader::from_bytes_endian(&be, Endianness::Big)
+                .unwrap()
+                .as_bytes(),
+            header.as_bytes()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000_0020);
+        let json = serde_json::to_string(&header).unwrap();
+        let parsed: BootableRegionDescriptorHeader = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.as_bytes(), header.as_bytes());
+
+        let [app] = xip_apps::<1>();
+        let json = serde_json::to_string(&app).unwrap();
+        let parsed: AppImageDescriptor = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.as_bytes(), app.as_bytes());
+
+        let error = ParseError::InvalidAppCrc {
+            slot: 1,
+            address: 0x1000,
+            found: 2,
+            expected: 3,
+        };
+        let json = serde_json::to_string(&error).unwrap();
+        let parsed: ParseError = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            parsed,
+            ParseError::InvalidAppCrc {
+                slot: 1,
+                address: 0x1000,
+                found: 2,
+                expected: 3
+            }
+        ));
+    }
+
+    #[test]
+    fn invalid_app_crc_reports_slot() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ParseError>();
+
+        let header = BootableRegionDescriptorHeader::new(3, 0, 0);
+        let mut apps = Aligned(xip_apps::<3>());
+        apps[2].app_version = 0xFF;
+
+        let result = BootableRegionDescriptors::from_header_and_app_regions(Aligned(header).ptr(), apps.ptr());
+        assert!(matches!(
+            result,
+            Err(ParseError::InvalidAppCrc { slot: 2, address, .. }) if address == &apps[2] as *const AppImageDescriptor as usize
+        ));
+    }
+
+    #[test]
+    fn header_version_compatibility() {
+        let mut header = BootableRegionDescriptorHeader::new(2, 0, 0x1000);
+        let aligned = Aligned(header);
+        let address = aligned.ptr();
+        assert!(BootableRegionDescriptorHeader::from_address_checked(address).is_ok());
+
+        // same major and minor, different patch
+        header.descriptor_version = (DESCRIPTOR_VERSION_MAJOR << 24) | (DESCRIPTOR_VERSION_MINOR << 8) | 0x7F;
+        header.header_crc = header.compute_crc();
+        let aligned = Aligned(header);
+        let address = aligned.ptr();
+        assert!(BootableRegionDescriptorHeader::from_address_checked(address).is_ok());
+
+        // different minor, which under major 0 may have shifted fields while keeping the header size
+        let found = (DESCRIPTOR_VERSION_MAJOR << 24) | ((DESCRIPTOR_VERSION_MINOR + 1) << 8);
+        header.descriptor_version = found;
+        header.header_crc = header.compute_crc();
+        let aligned = Aligned(header);
+        let address = aligned.ptr();
+        let result = BootableRegionDescriptorHeader::from_address_checked(address);
+        if DESCRIPTOR_VERSION_MAJOR == 0 {
+            assert!(matches!(
+                result,
+                Err(ParseError::IncompatibleVersion { found: f, expected }) if f == found && expected == DESCRIPTOR_VERSION
+            ));
+        } else {
+            assert!(result.is_ok());
+        }
+
+        // different major
+        let found = ((DESCRIPTOR_VERSION_MAJOR + 1) << 24) | (DESCRIPTOR_VERSION_MINOR << 8);
+        header.descriptor_version = found;
+        header.header_crc = header.compute_crc();
+        let aligned = Aligned(header);
+        let address = aligned.ptr();
+        assert!(matches!(
+            BootableRegionDescriptorHeader::from_address_checked(address),
+            Err(ParseError::IncompatibleVersion { found: f, expected }) if f == found && expected == DESCRIPTOR_VERSION
+        ));
+
+        // the unchecked parser is unaffected
+        assert!(BootableRegionDescriptorHeader::from_address(address).is_ok());
+    }
+
+    #[test]
+    fn highest_version_slots() {
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0);
+        let apps = Aligned([
+            AppImageDescriptor::new_execute_in_place_image(0, 1, 4, APP_IMAGE_FLAG_NONE, 0x1000_0000, 0x1000, 0),
+            AppImageDescriptor::new_execute_in_place_image(1, 2, 3, APP_IMAGE_FLAG_NONE, 0x1010_0000, 0x1000, 0),
+        ]);
+        let descriptors = region_from_parts(&header, &apps);
+
+        // the non-active slot holds the newer image
+        assert_eq!({ descriptors.highest_version_slot().unwrap().app_slot_number }, 1);
+        assert_eq!(
+            { descriptors.highest_security_version_slot().unwrap().app_slot_number },
+            0
+        );
+
+        // ties go to the lowest slot
+        let apps = Aligned([
+            AppImageDescriptor::new_execute_in_place_image(0, 2, 3, APP_IMAGE_FLAG_NONE, 0x1000_0000, 0x1000, 0),
+            AppImageDescriptor::new_execute_in_place_image(1, 2, 3, APP_IMAGE_FLAG_NONE, 0x1010_0000, 0x1000, 0),
+        ]);
+        let descriptors = region_from_parts(&header, &apps);
+        assert_eq!({ descriptors.highest_version_slot().unwrap().app_slot_number }, 0);
+        assert_eq!(
+            { descriptors.highest_security_version_slot().unwrap().app_slot_number },
+            0
+        );
+    }
+
+    #[test]
+    fn crc_algorithm_selection() {
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0x1000);
+        assert_eq!(header.compute_crc(), header.compute_crc_with(&CRC_32_ISO_HDLC));
+        assert_ne!(header.compute_crc(), header.compute_crc_with(&crc::CRC_32_CKSUM));
+        assert!(!header.is_crc_valid_with(&crc::CRC_32_CKSUM));
+
+        let mut app = AppImageDescriptor::new_execute_in_place_image(0, 1, 2, APP_IMAGE_FLAG_NONE, 0x1000, 0x1000, 0);
+        assert_eq!(app.compute_crc(), app.compute_crc_with(&CRC_32_ISO_HDLC));
+        assert_ne!(app.compute_crc(), app.compute_crc_with(&crc::CRC_32_CKSUM));
+
+        app.descriptor_crc = app.compute_crc_with(&crc::CRC_32_CKSUM);
+        assert!(app.is_crc_valid_with(&crc::CRC_32_CKSUM));
+        assert!(!app.is_crc_valid());
+    }
+
+    #[test]
+    fn try_from_bytes() {
+        let apps = xip_apps::<2>();
+        let region: [u8; BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE] = region_bytes(0, &apps);
+
+        let header: BootableRegionDescriptorHeader = region[..].try_into().unwrap();
+        assert_eq!({ header.num_app_slots }, 2);
+
+        let mut parsed = region[BOOT_REGION_DESCRIPTOR_SIZE..]
+            .chunks(APP_IMAGE_DESCRIPTOR_SIZE)
+            .map(AppImageDescriptor::try_from);
+        assert_eq!(parsed.next().unwrap().unwrap().as_bytes(), apps[0].as_bytes());
+        assert_eq!(parsed.next().unwrap().unwrap().as_bytes(), apps[1].as_bytes());
+
+        let mut corrupt = region;
+        corrupt[0] = 0;
+        assert!(matches!(
+            BootableRegionDescriptorHeader::try_from(&corrupt[..]),
+            Err(ParseError::InvalidSignature)
+        ));
+        corrupt[BOOT_REGION_DESCRIPTOR_SIZE] ^= 0xFF;
+        assert!(matches!(
+            AppImageDescriptor::try_from(&corrupt[BOOT_REGION_DESCRIPTOR_SIZE..]),
+            Err(ParseError::InvalidAppCrc { .. })
+        ));
+    }
+
+    #[test]
+    fn header_region_size() {
+        let header = BootableRegionDescriptorHeader::new(4, 0, 0x1000);
+        assert_eq!(
+            header.region_size().unwrap(),
+            BOOT_REGION_DESCRIPTOR_SIZE + 4 * APP_IMAGE_DESCRIPTOR_SIZE
+        );
+
+        // self-reported sizes are used rather than the compiled struct sizes
+        let mut header = header;
+        header.app_descriptor_size_bytes = 64;
+        assert_eq!(header.region_size().unwrap(), BOOT_REGION_DESCRIPTOR_SIZE + 4 * 64);
+
+        header.num_app_slots = u32::MAX;
+        header.app_descriptor_size_bytes = u32::MAX;
+        header.descriptor_header_size_bytes = u32::MAX;
+        if size_of::<usize>() <= size_of::<u32>() {
+            assert!(matches!(header.region_size(), Err(ParseError::ArithmeticOverflow)));
+        } else {
+            assert!(header.region_size().is_ok());
+        }
+    }
+
+    #[test]
+    fn app_descriptor_from_bytes_mut() {
+        let apps = xip_apps::<2>();
+        let mut region: [u8; BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE] = region_bytes(0, &apps);
+
+        let descriptor = AppImageDescriptor::from_bytes_mut(&mut region[BOOT_REGION_DESCRIPTOR_SIZE..], 1).unwrap();
+        descriptor.app_version += 1;
+        descriptor.flags |= APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK;
+        assert!(!descriptor.is_crc_valid());
+        descriptor.recompute_crc();
+
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        let updated = descriptors.get_app_at_slot(1).unwrap();
+        assert_eq!({ updated.app_version }, { apps[1].app_version } + 1);
+        assert!(updated.is_skip_crc_check());
+
+        assert!(matches!(
+            AppImageDescriptor::from_bytes_mut(&mut region[BOOT_REGION_DESCRIPTOR_SIZE..], 2),
+            Err(ParseError::BufferTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn bootable_region_descriptors_accessors() {
+        let header = BootableRegionDescriptorHeader::new(3, 2, 0);
+        let apps = Aligned(xip_apps::<3>());
+        let descriptors = region_from_parts(&header, &apps);
+
+        assert_eq!(descriptors.num_slots(), 3);
+        assert_eq!(descriptors.active_slot_index(), 2);
+        assert_eq!(descriptors.header().as_bytes(), header.as_bytes());
+    }
+
+    #[test]
+    fn execution_region_conflicts() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0);
+        let apps = Aligned([
+            AppImageDescriptor::new_execute_in_place_image(0, 0, 0, APP_IMAGE_FLAG_NONE, 0x1000_0000, 0x8000, 0),
+            AppImageDescriptor::new_ram_image(1, 0, 0, APP_IMAGE_FLAG_NONE, 0x1010_0000, 0x8000, 0x2000_0000, 0),
+        ]);
+        let descriptors = region_from_parts(&header, &apps);
+
+        // bootloader stack directly after the copied image
+        let safe = [(0x2000_8000, 0x1000), (0x1000_0000, 0x10_0000)];
+        assert!(descriptors.check_execution_safe(&safe).is_ok());
+
+        // bootloader stack overlapping the last word of the copied image
+        let conflicting = [(0x2000_7FFC, 0x1000)];
+        assert!(matches!(
+            descriptors.check_execution_safe(&conflicting),
+            Err(ParseError::ExecutionRegionConflict { slot: 1 })
+        ));
+
+        // XIP images are never copied
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0);
+        let descriptors = region_from_parts(&header, &apps);
+        assert!(descriptors.check_execution_safe(&[(0x1000_0000, 0x8000)]).is_ok());
+    }
+
+    #[test]
+    fn empty_header_and_default_app() {
+        let mut header = BootableRegionDescriptorHeader::empty(2, BOOT_REGION_DESCRIPTOR_SIZE as u32);
+        assert_eq!({ header.signature }, BOOT_REGION_DESCRIPTOR_SIGNATURE);
+        assert_eq!({ header.active_app_slot }, 0);
+        assert!(header.is_crc_valid());
+        assert!(header.set_active_slot(1).is_ok());
+
+        let app = AppImageDescriptor::default();
+        assert_eq!({ app.flags }, APP_IMAGE_FLAG_NONE);
+        assert_eq!({ app.stored_address }, 0);
+        assert!(app.is_crc_valid());
+        assert!(AppImageDescriptor::from_bytes(app.as_bytes()).is_ok());
+
+        let apps = [AppImageDescriptor::default(); 2];
+        let region = region_bytes::<{ BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE }>(1, &apps);
+        assert!(BootableRegionDescriptors::from_bytes(&region).is_ok());
+    }
+
+    #[test]
+    fn flag_names_and_unknown_flags() {
+        let app = AppImageDescriptor::builder()
+            .flags(APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK | 0x8000_0000)
+            .execution(0x2000_0000, 0x1000)
+            .build();
+
+        let mut names = app.flag_names();
+        assert_eq!(names.next(), Some("COPY_TO_EXECUTION_ADDRESS"));
+        assert_eq!(names.next(), Some("SKIP_IMAGE_CRC_CHECK"));
+        assert_eq!(names.next(), None);
+        assert_eq!(app.unknown_flags(), 0x8000_0000);
+
+        assert_eq!(AppImageDescriptor::default().flag_names().count(), 0);
+        assert_eq!(AppImageDescriptor::default().unknown_flags(), 0);
+    }
+
+    #[test]
+    fn strict_parsing_rejects_unknown_flags() {
+        let mut apps = xip_apps::<2>();
+        apps[1] = AppImageDescriptor::builder()
+            .slot(1)
+            .flags(APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK | 0x8000_0000)
+            .stored(0x1010_0000, 0x1000)
+            .build();
+        let region = region_bytes::<{ BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE }>(0, &apps);
+
+        // lenient by default for forward compatibility
+        assert!(BootableRegionDescriptors::from_bytes(&region).is_ok());
+
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes_strict(&region),
+            Err(ParseError::UnknownFlags {
+                slot: 1,
+                bits: 0x8000_0000
+            })
+        ));
+
+        // the region index is reported, not the slot the descriptor declares
+        apps[1].app_slot_number = 5;
+        apps[1].recompute_crc();
+        let region = region_bytes::<{ BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE }>(0, &apps);
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&region)
+                .unwrap()
+                .check_unknown_flags(),
+            Err(ParseError::UnknownFlags { slot: 1, .. })
+        ));
+
+        let region =
+            region_bytes::<{ BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE }>(0, &xip_apps::<2>());
+        assert!(BootableRegionDescriptors::from_bytes_strict(&region).is_ok());
+    }
+
+    #[test]
+    fn recompute_all_crcs_repairs_region() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let mut region = region_bytes::<N>(0, &xip_apps::<2>());
+
+        // edit the active slot and slot 1's app_version without updating either CRC
+        region[24..28].copy_from_slice(&1u32.to_le_bytes());
+        let app_version = BOOT_REGION_DESCRIPTOR_SIZE + APP_IMAGE_DESCRIPTOR_SIZE + 8;
+        region[app_version..app_version + 4].copy_from_slice(&7u32.to_le_bytes());
+        let sequence = core::mem::offset_of!(BootableRegionDescriptorHeader, sequence);
+        region[sequence..sequence + 4].copy_from_slice(&5u32.to_le_bytes());
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&region),
+            Err(ParseError::InvalidHeaderCrc { .. })
+        ));
+
+        assert!(recompute_all_crcs(&mut region).is_ok());
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!(descriptors.active_slot_index(), 1);
+        assert_eq!({ descriptors.get_active_slot().app_version }, 7);
+        // only the CRCs are rewritten, sequence is left as it was
+        assert_eq!({ descriptors.header().sequence }, 5);
+
+        assert!(matches!(
+            recompute_all_crcs(&mut region[..N - 1]),
+            Err(ParseError::BufferTooSmall { needed: N, got }) if got == N - 1
+        ));
+
+        // the signature still has to be there
+        region[0] = 0;
+        assert!(matches!(
+            recompute_all_crcs(&mut region),
+            Err(ParseError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn crc_checked_matches_inherent() {
+        fn check<T: CrcChecked>(descriptor: &T) -> (u32, bool) {
+            (descriptor.compute_crc(), descriptor.is_crc_valid())
+        }
+
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000);
+        assert_eq!(check(&header), (header.compute_crc(), true));
+        assert_eq!(CrcChecked::stored_crc(&header), { header.header_crc });
+
+        let mut app = xip_apps::<1>()[0];
+        assert_eq!(check(&app), (app.compute_crc(), true));
+        assert_eq!(
+            CrcChecked::compute_crc_with(&app, &crc::CRC_32_CKSUM),
+            app.compute_crc_with(&crc::CRC_32_CKSUM)
+        );
+
+        app.app_version += 1;
+        assert!(!check(&app).1);
+    }
+
+    #[test]
+    fn validate_image_explicit_and_in_buffer() {
+        // image at offset 0x10, its CRC immediately after
+        let mut region = [0u8; 0x60];
+        for (i, byte) in region[0x10..0x50].iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let crc = Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(&region[0x10..0x50]);
+        region[0x50..0x54].copy_from_slice(&crc.to_le_bytes());
+
+        let app = AppImageDescriptor::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0x10, 0x40, 0x50);
+        assert!(app.validate_image(&region[0x10..0x50], crc).is_ok());
+        assert!(matches!(
+            app.validate_image(&region[0x10..0x50], !crc),
+            Err(ParseError::InvalidImageCrc { found, expected }) if found == crc && expected == !crc
+        ));
+
+        assert!(app.validate_image_in_buffer(&region).is_ok());
+        assert!(matches!(
+            app.validate_image_in_buffer(&region[..0x52]),
+            Err(ParseError::BufferTooSmall {
+                needed: 0x54,
+                got: 0x52
+            })
+        ));
+
+        region[0x20] ^= 0xFF;
+        assert!(matches!(
+            app.validate_image_in_buffer(&region),
+            Err(ParseError::InvalidImageCrc { expected, .. }) if expected == crc
+        ));
+
+        // skip flag short circuits, even when the image isn't in the buffer
+        let app = AppImageDescriptor::new_execute_in_place_image(
+            0,
+            1,
+            0,
+            APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+            0x10,
+            0x40,
+            0x50,
+        );
+        assert!(app.validate_image_in_buffer(&region).is_ok());
+        assert!(app.validate_image_in_buffer(&[]).is_ok());
+    }
+
+    #[test]
+    fn header_size_mismatch() {
+        let mut header = BootableRegionDescriptorHeader::new(1, 0, 0x1000);
+        header.app_descriptor_size_bytes = 64;
+        header.header_crc = header.compute_crc();
+        assert!(matches!(
+            BootableRegionDescriptorHeader::from_bytes(header.as_bytes()),
+            Err(ParseError::SizeMismatch {
+                field: SizeField::AppDescriptorSize,
+                found: 64,
+                expected: 68
+            })
+        ));
+
+        let mut header = BootableRegionDescriptorHeader::new(1, 0, 0x1000);
+        header.descriptor_header_size_bytes = 36;
+        header.header_crc = header.compute_crc();
+        assert!(matches!(
+            BootableRegionDescriptors::from_address(Aligned(header).ptr()),
+            Err(ParseError::SizeMismatch {
+                field: SizeField::DescriptorHeaderSize,
+                found: 36,
+                expected: 40
+            })
+        ));
+    }
+
+    #[test]
+    fn build_region_in_static() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        static APPS: [AppImageDescriptor; 2] = [
+            AppImageDescriptor::new_execute_in_place_image(
+                0,
+                1,
+                0,
+                APP_IMAGE_FLAG_NONE,
+                0x1000_0000,
+                0x1000,
+                0x1000_1000,
+            ),
+            AppImageDescriptor::new_ram_image(
+                1,
+                2,
+                0,
+                APP_IMAGE_FLAG_NONE,
+                0x1010_0000,
+                0x1000,
+                0x2000_0000,
+                0x1010_1000,
+            ),
+        ];
+        static REGION: [u8; N] = build_region(
+            BootableRegionDescriptorHeader::new(2, 1, BOOT_REGION_DESCRIPTOR_SIZE as u32),
+            &APPS,
+        );
+
+        assert_eq!(REGION, region_bytes::<N>(1, &APPS));
+
+        let descriptors = BootableRegionDescriptors::from_bytes(&REGION).unwrap();
+        assert_eq!(descriptors.get_active_slot().as_bytes(), APPS[1].as_bytes());
+    }
+
+    #[test]
+    fn strict_parsing_rejects_slot_number_mismatch() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let mut apps = xip_apps::<3>();
+        apps[2] = AppImageDescriptor::builder()
+            .slot(0)
+            .stored(0x1020_0000, 0x1000)
+            .build();
+        let region = region_bytes::<N>(0, &apps);
+
+        // lenient by default for backward compatibility
+        assert!(BootableRegionDescriptors::from_bytes(&region).is_ok());
+
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes_strict(&region),
+            Err(ParseError::SlotNumberMismatch { index: 2, declared: 0 })
+        ));
+        assert!(BootableRegionDescriptors::from_bytes_strict(&region_bytes::<N>(0, &xip_apps::<3>())).is_ok());
+    }
+
+    #[test]
+    fn descriptors_compare_equal_after_round_trip() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000);
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        header.write_to(&mut buf).unwrap();
+        assert_eq!(BootableRegionDescriptorHeader::from_bytes(&buf).unwrap(), header);
+        assert_ne!(BootableRegionDescriptorHeader::new(2, 0, 0x1000), header);
+
+        let app = xip_apps::<2>()[1];
+        let mut buf = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        app.write_to(&mut buf).unwrap();
+        assert_eq!(AppImageDescriptor::from_bytes(&buf).unwrap(), app);
+
+        // only the CRC differs
+        let mut stale = app;
+        stale.descriptor_crc ^= 1;
+        assert_ne!(stale, app);
+    }
+
+    #[test]
+    fn duplicate_slot_numbers() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let region = region_bytes::<N>(0, &xip_apps::<3>());
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!(descriptors.find_duplicate_slot_numbers(), None);
+
+        let mut apps = xip_apps::<3>();
+        apps[2] = AppImageDescriptor::builder()
+            .slot(1)
+            .stored(0x1020_0000, 0x1000)
+            .build();
+        let region = region_bytes::<N>(0, &apps);
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!(descriptors.find_duplicate_slot_numbers(), Some((1, 2)));
+    }
+
+    #[test]
+    fn crc_covered_ranges() {
+        let (start, len) = BootableRegionDescriptorHeader::crc_covered_range();
+        assert_eq!((start, len), (0, BOOT_REGION_DESCRIPTOR_SIZE - 4));
+
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000);
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        header.write_to(&mut buf).unwrap();
+        let crc = Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(&buf[start..start + len]);
+        assert_eq!(crc, { header.header_crc });
+
+        let (start, len) = AppImageDescriptor::crc_covered_range();
+        assert_eq!((start, len), (0, APP_IMAGE_DESCRIPTOR_SIZE - 4));
+
+        let app = xip_apps::<1>()[0];
+        let mut buf = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        app.write_to(&mut buf).unwrap();
+        let crc = Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(&buf[start..start + len]);
+        assert_eq!(crc, { app.descriptor_crc });
+    }
+
+    #[test]
+    fn copy_plan_for_xip_and_ram() {
+        let xip = AppImageDescriptor::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0x1000_0000, 0x8000, 0);
+        assert_eq!(xip.copy_plan(), None);
+
+        let ram = AppImageDescriptor::new_ram_image(1, 1, 0, APP_IMAGE_FLAG_NONE, 0x1010_0000, 0x8000, 0x2000_0000, 0);
+        assert_eq!(
+            ram.copy_plan(),
+            Some(CopyPlan {
+                src: 0x1010_0000,
+                dst: 0x2000_0000,
+                len: 0x8000
+            })
+        );
+    }
+
+    #[test]
+    fn vector_table_address_follows_copy_flag() {
+        let xip = AppImageDescriptor::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0x1000_0000, 0x8000, 0);
+        assert_eq!(xip.vector_table_address(), 0x1000_0000);
+
+        let ram = AppImageDescriptor::new_ram_image(1, 1, 0, APP_IMAGE_FLAG_NONE, 0x1010_0000, 0x8000, 0x2000_0000, 0);
+        assert_eq!(ram.vector_table_address(), 0x2000_0000);
+
+        // an XIP descriptor built with a stale execution_address still boots from where it's stored
+        let xip = AppImageDescriptor::builder().stored(0x1000_0000, 0x8000).build();
+        let mut stale = xip;
+        stale.execution_address = 0x2000_0000;
+        assert_eq!(stale.vector_table_address(), 0x1000_0000);
+    }
+
+    #[test]
+    fn recovery_slot_fallback() {
+        let header = BootableRegionDescriptorHeader::new(3, 1, 0);
+        let mut apps = Aligned(xip_apps::<3>());
+        apps[2] = AppImageDescriptor::builder()
+            .slot(2)
+            .flags(APP_IMAGE_FLAG_RECOVERY)
+            .stored(0x1020_0000, 0x1000)
+            .build();
+
+        // active valid
+        let apps_ptr = apps.as_mut_ptr();
+        let descriptors =
+            BootableRegionDescriptors::from_header_and_app_regions(Aligned(header).ptr(), apps_ptr as *const u32)
+                .unwrap();
+        assert_eq!({ descriptors.recovery_slot().unwrap().app_slot_number }, 2);
+        assert_eq!({ descriptors.boot_or_recovery().unwrap().app_slot_number }, 1);
+
+        // active descriptor corrupted after construction, e.g. an interrupted update
+        unsafe { (*apps_ptr.add(1)).app_version ^= 1 };
+        assert_eq!({ descriptors.boot_or_recovery().unwrap().app_slot_number }, 2);
+
+        // a slot before the recovery slot corrupted after construction is skipped
+        unsafe { (*apps_ptr).app_version ^= 1 };
+        assert_eq!({ descriptors.recovery_slot().unwrap().app_slot_number }, 2);
+        assert_eq!({ descriptors.boot_or_recovery().unwrap().app_slot_number }, 2);
+
+        // an active recovery slot is only booted as the fallback
+        let header = BootableRegionDescriptorHeader::new(3, 2, 0);
+        let mut apps = Aligned(xip_apps::<3>());
+        apps[2] = AppImageDescriptor::builder()
+            .slot(2)
+            .flags(APP_IMAGE_FLAG_RECOVERY)
+            .stored(0x1020_0000, 0x1000)
+            .build();
+        let descriptors = region_from_parts(&header, &apps);
+        assert_eq!({ descriptors.boot_or_recovery().unwrap().app_slot_number }, 2);
+
+        // no recovery slot to fall back to
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0);
+        let mut apps = Aligned(xip_apps::<2>());
+        let apps_ptr = apps.as_mut_ptr();
+        let descriptors =
+            BootableRegionDescriptors::from_header_and_app_regions(Aligned(header).ptr(), apps_ptr as *const u32)
+                .unwrap();
+        assert!(descriptors.recovery_slot().is_none());
+        unsafe { (*apps_ptr).app_version ^= 1 };
+        assert!(matches!(
+            descriptors.boot_or_recovery(),
+            Err(ParseError::InvalidAppCrc { slot: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn view_round_trips() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000);
+        let view = HeaderView::from(header);
+        let active: &u32 = &view.active_app_slot;
+        assert_eq!(*active, 1);
+        assert_eq!(view.header_crc, { header.header_crc });
+        assert_eq!(BootableRegionDescriptorHeader::from(view), header);
+
+        let app = xip_apps::<2>()[1];
+        let mut view = AppView::from(app);
+        assert_eq!(view.stored_address, 0x1010_0000);
+        assert_eq!(AppImageDescriptor::from(view), app);
+
+        // edits made through the view keep the CRC untouched
+        view.app_version = 9;
+        let edited = AppImageDescriptor::from(view);
+        assert!(!edited.is_crc_valid());
+        assert_eq!({ edited.descriptor_crc }, { app.descriptor_crc });
+    }
+
+    #[test]
+    fn inactive_slots_skip_active() {
+        let header = BootableRegionDescriptorHeader::new(3, 1, 0);
+        let apps = Aligned(xip_apps::<3>());
+        let descriptors = region_from_parts(&header, &apps);
+        let mut inactive = descriptors.inactive_slots();
+        assert_eq!(inactive.next(), Some(0));
+        assert_eq!(inactive.next(), Some(2));
+        assert_eq!(inactive.next(), None);
+        assert_eq!(descriptors.first_inactive_slot(), Some(0));
+
+        let header = BootableRegionDescriptorHeader::new(1, 0, 0);
+        let descriptors = region_from_parts(&header, &apps);
+        assert_eq!(descriptors.first_inactive_slot(), None);
+    }
+
+    #[test]
+    fn descriptor_crc_algorithm_is_discoverable() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000);
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        header.write_to(&mut buf).unwrap();
+        let crc = Crc::<u32>::new(&DESCRIPTOR_CRC_ALGORITHM).checksum(&buf[..BOOT_REGION_DESCRIPTOR_SIZE - 4]);
+        assert_eq!(header.compute_crc(), crc);
+
+        let app = xip_apps::<1>()[0];
+        let mut buf = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        app.write_to(&mut buf).unwrap();
+        let crc = Crc::<u32>::new(&DESCRIPTOR_CRC_ALGORITHM).checksum(&buf[..APP_IMAGE_DESCRIPTOR_SIZE - 4]);
+        assert_eq!(app.compute_crc(), crc);
+
+        assert_eq!(DESCRIPTOR_CRC_ALGORITHM.poly, 0x04C1_1DB7);
+        assert_eq!(DESCRIPTOR_CRC_ALGORITHM.check, 0xCBF4_3926);
+    }
+
+    #[test]
+    fn bounded_parsing() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let region = region_bytes::<N>(0, &xip_apps::<2>());
+        assert!(BootableRegionDescriptors::from_bytes_bounded(&region, 2).is_ok());
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes_bounded(&region, 1),
+            Err(ParseError::TooManySlots { found: 2, max: 1 })
+        ));
+
+        // absurd slot count from a bit flipped or malicious header
+        let mut region = region;
+        BootableRegionDescriptorHeader::new(u32::MAX, 0, BOOT_REGION_DESCRIPTOR_SIZE as u32)
+            .write_to(&mut region)
+            .unwrap();
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes_bounded(&region, 8),
+            Err(ParseError::TooManySlots {
+                found: u32::MAX,
+                max: 8
+            })
+        ));
+
+        // within the slot limit, but the descriptors would run past the buffer
+        BootableRegionDescriptorHeader::new(4, 0, BOOT_REGION_DESCRIPTOR_SIZE as u32)
+            .write_to(&mut region)
+            .unwrap();
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes_bounded(&region, 8),
+            Err(ParseError::BufferTooSmall { needed, got: N }) if needed == BOOT_REGION_DESCRIPTOR_SIZE + 4 * APP_IMAGE_DESCRIPTOR_SIZE
+        ));
+    }
+
+    #[test]
+    fn descriptor_offsets() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let mut region = region_bytes::<N>(2, &xip_apps::<3>());
+        let header = BootableRegionDescriptorHeader::from_bytes(&region).unwrap();
+
+        assert_eq!(header.descriptor_offset(0).unwrap(), 40);
+        assert_eq!(header.descriptor_offset(1).unwrap(), 108);
+        assert!(matches!(header.descriptor_offset(3), Err(ParseError::InvalidAppSlot)));
+
+        // patch only the active descriptor in place
+        let offset = header.active_descriptor_offset().unwrap();
+        assert_eq!(offset, 176);
+        let mut app = AppImageDescriptor::from_bytes(&region[offset..]).unwrap();
+        app.app_version = 9;
+        app.recompute_crc();
+        app.write_to(&mut region[offset..]).unwrap();
+
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!({ descriptors.get_active_slot().app_version }, 9);
+    }
+
+    #[test]
+    fn image_partition_bounds() {
+        let app =
+            AppImageDescriptor::new_execute_in_place_image(3, 1, 0, APP_IMAGE_FLAG_NONE, 0x1001_0000, 0x1_0000, 0);
+
+        // exactly fits
+        assert!(app.fits_in_partition(0x1001_0000, 0x1_0000));
+        assert!(app.validate_partition(0x1000_0000, 0x2_0000).is_ok());
+
+        // one byte over
+        assert!(!app.fits_in_partition(0x1001_0000, 0xFFFF));
+        assert!(matches!(
+            app.validate_partition(0x1001_0000, 0xFFFF),
+            Err(ParseError::ImageExceedsPartition { slot: 3 })
+        ));
+
+        // starts below the partition
+        assert!(!app.fits_in_partition(0x1001_0001, 0x10_0000));
+
+        // partition reaching the top of the address space doesn't overflow
+        let app =
+            AppImageDescriptor::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0xFFFF_0000, 0x1_0000, 0);
+        assert!(app.fits_in_partition(0xFFFF_0000, 0x1_0000));
+        assert!(app.fits_in_partition(0xFFFF_0000, u32::MAX));
+    }
+
+    #[test]
+    fn from_reader_slice_backed() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let region = region_bytes::<N>(2, &xip_apps::<3>());
+
+        let descriptors = BootableRegionDescriptors::from_reader(&region, 0).unwrap();
+        assert_eq!(descriptors.num_slots(), 3);
+        assert_eq!(descriptors.get_active_slot(), xip_apps::<3>()[2]);
+        assert_eq!(descriptors.iter().count(), 3);
+
+        let slice = &region[..N - 1];
+        assert!(matches!(
+            BootableRegionDescriptors::from_reader(&slice, 0),
+            Err(ParseError::FlashReadFailed { address }) if address as usize == N - APP_IMAGE_DESCRIPTOR_SIZE
+        ));
+    }
+
+    #[test]
+    fn from_reader_driver_backed() {
+        /// Flash mapped at 0x1000_0000 behind a driver that can be made to fail
+        struct Driver {
+            flash: [u8; BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE],
+            fail: bool,
+        }
+
+        impl FlashRead for Driver {
+            type Error = ();
+
+            fn read(&self, addr: u32, buf: &mut [u8]) -> Result<(), ()> {
+                if self.fail {
+                    return Err(());
+                }
+                let offset = (addr - 0x1000_0000) as usize;
+                buf.copy_from_slice(&self.flash[offset..offset + buf.len()]);
+                Ok(())
+            }
+        }
+
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000_0000 + BOOT_REGION_DESCRIPTOR_SIZE as u32);
+        let mut driver = Driver {
+            flash: build_region(header, &xip_apps::<2>()),
+            fail: false,
+        };
+
+        let descriptors = BootableRegionDescriptors::from_reader(&driver, 0x1000_0000).unwrap();
+        assert_eq!({ descriptors.get_active_slot().app_version }, 2);
+
+        // corrupt descriptors report their flash address
+        driver.flash[BOOT_REGION_DESCRIPTOR_SIZE + 8] ^= 1;
+        assert!(matches!(
+            BootableRegionDescriptors::from_reader(&driver, 0x1000_0000),
+            Err(ParseError::InvalidAppCrc {
+                slot: 0,
+                address: 0x1000_0028,
+                ..
+            })
+        ));
+
+        driver.fail = true;
+        assert!(matches!(
+            BootableRegionDescriptors::from_reader(&driver, 0x1000_0000),
+            Err(ParseError::FlashReadFailed { address: 0x1000_0000 })
+        ));
+    }
+
+    #[test]
+    fn descriptor_version_skew() {
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0);
+        let mut apps = Aligned(xip_apps::<2>());
+        let descriptors = region_from_parts(&header, &apps);
+        assert!(descriptors.descriptor_versions_consistent().is_ok());
+
+        apps[1].descriptor_version = DESCRIPTOR_VERSION + 1;
+        apps[1].recompute_crc();
+        let descriptors = region_from_parts(&header, &apps);
+        assert!(matches!(
+            descriptors.descriptor_versions_consistent(),
+            Err(ParseError::VersionSkew { slot: 1, found, expected })
+                if found == DESCRIPTOR_VERSION + 1 && expected == DESCRIPTOR_VERSION
+        ));
+    }
+
+    #[test]
+    fn version_parts_round_trip() {
+        for (major, minor, patch) in [
+            (0, 0, 0),
+            (1, 2, 3),
+            (0xFF, 0xFFFF, 0xFF),
+            (0x80, 0x8000, 0x80),
+            (0, 0x100, 0),
+        ] {
+            let version = make_version(major, minor, patch);
+            assert_eq!(version_parts(version), (major, minor, patch));
+        }
+
+        assert_eq!(make_version(0x12, 0x3456, 0x78), 0x1234_5678);
+        assert_eq!(version_parts(0xFFFF_FFFF), (0xFF, 0xFFFF, 0xFF));
+        assert_eq!(
+            make_version(
+                DESCRIPTOR_VERSION_MAJOR as u8,
+                DESCRIPTOR_VERSION_MINOR as u16,
+                DESCRIPTOR_VERSION_PATCH as u8
+            ),
+            DESCRIPTOR_VERSION
+        );
+    }
+
+    #[test]
+    fn write_report_lists_every_slot() {
+        use core::fmt::Write;
+
+        let header = BootableRegionDescriptorHeader::new(3, 1, 0);
+        let apps = Aligned(xip_apps::<3>());
+        let descriptors = region_from_parts(&header, &apps);
+
+        let mut report = heapless::String::<1024>::new();
+        descriptors.write_report(&mut report).unwrap();
+
+        let mut lines = report.lines();
+        assert!(lines.next().unwrap().starts_with("header"));
+        for slot in 0..3 {
+            let mut prefix = heapless::String::<16>::new();
+            write!(prefix, "slot {slot:>3}").unwrap();
+
+            let line = lines.next().unwrap();
+            assert!(line.starts_with(prefix.as_str()));
+            assert!(line.contains("crc ok"));
+            assert_eq!(line.ends_with("(active)"), slot == 1);
+        }
+        assert!(report.contains("stored 0x10100000+0x00001000"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn empty_image_rejected() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let mut apps = xip_apps::<2>();
+        apps[1] = AppImageDescriptor::new_ram_image(1, 1, 0, APP_IMAGE_FLAG_NONE, 0x1010_0000, 0, 0x2000_0000, 0);
+        assert!(apps[1].is_empty_image());
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&region_bytes::<N>(0, &apps)),
+            Err(ParseError::EmptyImage { slot: 1 })
+        ));
+
+        // skipping the CRC check marks the size as intentionally unknown
+        apps[1] = AppImageDescriptor::new_ram_image(
+            1,
+            1,
+            0,
+            APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+            0x1010_0000,
+            0,
+            0x2000_0000,
+            0,
+        );
+        assert!(BootableRegionDescriptors::from_bytes(&region_bytes::<N>(0, &apps)).is_ok());
+
+        // blank, not yet populated slots describe no image at all
+        apps[1] = AppImageDescriptor::default();
+        assert!(apps[1].is_blank());
+        assert!(!apps[1].is_empty_image());
+        assert!(BootableRegionDescriptors::from_bytes(&region_bytes::<N>(0, &apps)).is_ok());
+
+        // but a blank active slot is never booted, falling back to recovery if there is one
+        let region = region_bytes::<N>(1, &apps);
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert!(matches!(
+            descriptors.select_boot_slot(0),
+            Err(ParseError::EmptyImage { slot: 1 })
+        ));
+        apps[0].flags = APP_IMAGE_FLAG_RECOVERY;
+        apps[0].recompute_crc();
+        let region = region_bytes::<N>(1, &apps);
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!({ descriptors.boot_or_recovery().unwrap().app_slot_number }, 0);
+    }
+
+    #[test]
+    fn region_digest_covers_every_descriptor() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let region = region_bytes::<N>(0, &xip_apps::<2>());
+        let digest = BootableRegionDescriptors::from_bytes(&region)
+            .unwrap()
+            .region_digest(&region)
+            .unwrap();
+        let crc = Crc::<u32>::new(&DESCRIPTOR_CRC_ALGORITHM);
+        let mut expected = crc.digest();
+        expected.update(&region[..36]);
+        expected.update(&region[40..104]);
+        expected.update(&region[108..172]);
+        assert_eq!(digest, expected.finalize());
+
+        // any field change in any descriptor changes the digest
+        for (slot, active) in [(0, 1), (1, 0)] {
+            let mut apps = xip_apps::<2>();
+            apps[slot].security_version = 1;
+            apps[slot].recompute_crc();
+            let changed = region_bytes::<N>(active, &apps);
+            let descriptors = BootableRegionDescriptors::from_bytes(&changed).unwrap();
+            assert_ne!(descriptors.region_digest(&changed).unwrap(), digest);
+        }
+
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert!(matches!(
+            descriptors.region_digest(&region[..N - 1]),
+            Err(ParseError::BufferTooSmall { needed: N, .. })
+        ));
+    }
+
+    #[test]
+    fn crc_address_placement() {
+        let place = |stored_crc_address| {
+            AppImageDescriptor::new_execute_in_place_image(
+                2,
+                1,
+                0,
+                APP_IMAGE_FLAG_NONE,
+                0x1000,
+                0x1000,
+                stored_crc_address,
+            )
+        };
+
+        // just before and just after the image
+        assert!(place(0x0FFC).crc_address_is_external());
+        assert!(place(0x2000).validate_crc_address().is_ok());
+
+        // inside, including a word straddling either end
+        assert!(!place(0x1800).crc_address_is_external());
+        assert!(!place(0x0FFE).crc_address_is_external());
+        assert!(!place(0x1FFE).crc_address_is_external());
+        assert!(matches!(
+            place(0x1000).validate_crc_address(),
+            Err(ParseError::CrcAddressInsideImage { slot: 2 })
+        ));
+    }
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn parse_descriptors_zero_copy() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let region = region_bytes::<N>(1, &xip_apps::<3>());
+
+        let (header, apps) = parse_descriptors(&region).unwrap();
+        assert_eq!({ header.active_app_slot }, 1);
+        assert_eq!(apps, &xip_apps::<3>()[..]);
+        assert_eq!(
+            apps.as_ptr() as *const u8,
+            region[BOOT_REGION_DESCRIPTOR_SIZE..].as_ptr()
+        );
+
+        // odd offsets are fine, descriptors are packed
+        let mut shifted = [0u8; N + 1];
+        BootableRegionDescriptorHeader::new(3, 1, BOOT_REGION_DESCRIPTOR_SIZE as u32 + 1)
+            .write_to(&mut shifted)
+            .unwrap();
+        shifted[BOOT_REGION_DESCRIPTOR_SIZE + 1..].copy_from_slice(&region[BOOT_REGION_DESCRIPTOR_SIZE..]);
+        assert_eq!(parse_descriptors(&shifted).unwrap().1, apps);
+
+        let mut corrupt = region;
+        corrupt[N - 10] ^= 1;
+        assert!(matches!(
+            parse_descriptors(&corrupt),
+            Err(ParseError::InvalidAppCrc { slot: 2, .. })
+        ));
+        assert!(matches!(
+            parse_descriptors(&region[..N - 1]),
+            Err(ParseError::BufferTooSmall { needed: N, .. })
+        ));
+    }
+
+    #[test]
+    fn boot_attempt_counting() {
+        let mut app = xip_apps::<1>()[0];
+        assert_eq!({ app.boot_attempts }, 0);
+        assert!(!app.boot_attempts_exceeded(3));
+
+        for _ in 0..3 {
+            app.increment_boot_attempts();
+            assert!(app.is_crc_valid());
+        }
+        assert_eq!({ app.boot_attempts }, 3);
+        assert!(!app.boot_attempts_exceeded(4));
+        assert!(app.boot_attempts_exceeded(3));
+
+        // the incremented count round trips through flash
+        let mut buf = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        app.write_to(&mut buf).unwrap();
+        assert_eq!(AppImageDescriptor::from_bytes(&buf).unwrap(), app);
+
+        app.clear_boot_attempts();
+        assert!(app.is_crc_valid());
+        assert!(!app.boot_attempts_exceeded(1));
+
+        app.boot_attempts = u32::MAX;
+        app.increment_boot_attempts();
+        assert_eq!({ app.boot_attempts }, u32::MAX);
+    }
+
+    #[test]
+    fn active_slot_recheck_catches_late_corruption() {
+        /// Flash whose contents can change after the region is loaded
+        struct Flash(core::cell::RefCell<[u8; BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE]>);
+
+        impl FlashRead for Flash {
+            type Error = ParseError;
+
+            fn read(&self, addr: u32, buf: &mut [u8]) -> Result<(), ParseError> {
+                self.0.borrow().read(addr, buf)
+            }
+        }
+
+        let flash = Flash(core::cell::RefCell::new(region_bytes(1, &xip_apps::<2>())));
+        let descriptors = BootableRegionDescriptors::from_reader(&flash, 0).unwrap();
+        assert_eq!(
+            descriptors.active_slot_rechecked().unwrap(),
+            descriptors.get_active_slot()
+        );
+
+        // bit rot in the active descriptor between the scan and the jump
+        flash.0.borrow_mut()[BOOT_REGION_DESCRIPTOR_SIZE + APP_IMAGE_DESCRIPTOR_SIZE + 8] ^= 1;
+        assert!(matches!(
+            descriptors.active_slot_rechecked(),
+            Err(ParseError::InvalidAppCrc { slot: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn encrypted_flag() {
+        let plain = AppImageDescriptor::new_ram_image(0, 1, 0, 0, 0x1000, 0x100, 0x2000_0000, 0x1100);
+        assert!(!plain.is_encrypted());
+
+        let app =
+            AppImageDescriptor::new_ram_image(0, 1, 0, APP_IMAGE_FLAG_ENCRYPTED, 0x1000, 0x100, 0x2000_0000, 0x1100);
+        assert!(app.is_encrypted());
+        assert!(app.flag_names().any(|name| name == "ENCRYPTED"));
+        assert_eq!(app.unknown_flags(), 0);
+        assert_eq!(APP_IMAGE_FLAG_ALL & APP_IMAGE_FLAG_ENCRYPTED, APP_IMAGE_FLAG_ENCRYPTED);
+    }
+
+    #[test]
+    fn header_const_validation() {
+        assert!(validate_header_const(&BootableRegionDescriptorHeader::new(1, 0, 0)));
+        assert!(!validate_header_const(&BootableRegionDescriptorHeader::new(0, 0, 0)));
+        assert!(!validate_header_const(&BootableRegionDescriptorHeader::new(2, 2, 0)));
+
+        let mut header = BootableRegionDescriptorHeader::new(2, 1, 0);
+        header.signature = 0;
+        assert!(!validate_header_const(&header));
+    }
+
+    #[test]
+    fn relocate_region() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000_0000 + BOOT_REGION_DESCRIPTOR_SIZE as u32);
+        let apps = xip_apps::<2>();
+        let mut region: [u8; N] = build_region(header, &apps);
+
+        assert!(BootableRegionDescriptors::relocate(&mut region, 0x1000_0000, 0x1800_0000).is_ok());
+        let header = BootableRegionDescriptorHeader::from_bytes(&region).unwrap();
+        assert_eq!(
+            { header.app_descriptor_base_address },
+            0x1800_0000 + BOOT_REGION_DESCRIPTOR_SIZE as u32
+        );
+
+        // reparse at the new base through a reader mapped there
+        struct Flash([u8; N]);
+        impl FlashRead for Flash {
+            type Error = ParseError;
+
+            fn read(&self, addr: u32, buf: &mut [u8]) -> Result<(), ParseError> {
+                let offset = addr.checked_sub(0x1800_0000).ok_or(ParseError::ArithmeticOverflow)?;
+                self.0.read(offset, buf)
+            }
+        }
+        let flash = Flash(region);
+        let descriptors = BootableRegionDescriptors::from_reader(&flash, 0x1800_0000).unwrap();
+        assert_eq!(descriptors.active_slot_index(), 1);
+        assert_eq!(descriptors.get_app_at_slot(0).unwrap(), apps[0]);
+
+        // app descriptors before the old base can't be relocated
+        assert!(matches!(
+            BootableRegionDescriptors::relocate(&mut region, 0x2000_0000, 0),
+            Err(ParseError::ArithmeticOverflow)
+        ));
+    }
+
+    #[test]
+    fn patch_bytes_in_place() {
+        let mut header = BootableRegionDescriptorHeader::new(2, 0, 0x1000);
+        // active_app_slot
+        header.as_bytes_mut()[24] = 1;
+        assert!(!header.is_crc_valid());
+        header.recompute_crc();
+        assert!(header.is_crc_valid());
+        assert_eq!({ header.active_app_slot }, 1);
+
+        let mut app = xip_apps::<1>()[0];
+        // app_version
+        app.as_bytes_mut()[8] = 9;
+        assert!(!app.is_crc_valid());
+        app.recompute_crc();
+        assert!(app.is_crc_valid());
+        assert_eq!({ app.app_version }, 9);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parse_error_into_io_error() {
+        let err: std::io::Error = ParseError::EmptyImage { slot: 2 }.into();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(std::format!("{err}"), "empty image in slot 2");
+    }
+
+    #[test]
+    fn descriptor_base_overlapping_header() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + APP_IMAGE_DESCRIPTOR_SIZE;
+        let apps = xip_apps::<1>();
+
+        let header = BootableRegionDescriptorHeader::new(1, 0, BOOT_REGION_DESCRIPTOR_SIZE as u32);
+        assert_eq!(header.slot_stride(), APP_IMAGE_DESCRIPTOR_SIZE as u32);
+        assert!(header.validate_descriptor_base(0).is_ok());
+        // descriptors ending right where the header starts are fine too
+        assert!(header.validate_descriptor_base(N).is_ok());
+        assert!(matches!(
+            header.validate_descriptor_base(N - 1),
+            Err(ParseError::DescriptorBaseOverlapsHeader)
+        ));
+
+        let header = BootableRegionDescriptorHeader::new(1, 0, 16);
+        let region: [u8; N] = build_region(header, &apps);
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&region),
+            Err(ParseError::DescriptorBaseOverlapsHeader)
+        ));
+        #[cfg(target_endian = "little")]
+        assert!(matches!(
+            parse_descriptors(&region),
+            Err(ParseError::DescriptorBaseOverlapsHeader)
+        ));
+        assert!(matches!(
+            BootableRegionDescriptors::from_reader(&region, 0),
+            Err(ParseError::DescriptorBaseOverlapsHeader)
+        ));
+    }
+
+    #[test]
+    fn get_active_slot_does_not_panic() {
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0);
+        let mut apps = Aligned(xip_apps::<2>());
+        let expected = apps[0];
+        let active = apps.as_mut_ptr();
+        let descriptors =
+            BootableRegionDescriptors::from_header_and_app_regions(Aligned(header).ptr(), active as *const u32)
+                .unwrap();
+        assert_eq!(descriptors.get_active_slot(), expected);
+
+        // corrupt the active descriptor behind the manager's back, as flash changing underneath it would
+        unsafe { (*active).app_version ^= 1 };
+        assert_eq!(descriptors.get_active_slot(), expected);
+        assert!(matches!(
+            descriptors.active_slot_rechecked(),
+            Err(ParseError::InvalidAppCrc { slot: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn descriptor_version_struct() {
+        use core::fmt::Write;
+
+        let version = DescriptorVersion::from(0x0102_0304);
+        assert_eq!(
+            version,
+            DescriptorVersion {
+                major: 1,
+                minor: 0x0203,
+                patch: 4
+            }
+        );
+        assert_eq!(u32::from(version), 0x0102_0304);
+        assert_eq!(DescriptorVersion::CURRENT.to_u32(), DESCRIPTOR_VERSION);
+        assert_eq!(DescriptorVersion::CURRENT.major as u32, DESCRIPTOR_VERSION_MAJOR);
+
+        let mut display = heapless::String::<16>::new();
+        write!(display, "{version}").unwrap();
+        assert_eq!(display, "1.515.4");
+
+        assert!(version.is_compatible_with(&DescriptorVersion::from(0x01FF_FFFF)));
+        assert!(!version.is_compatible_with(&DescriptorVersion::from(0x0202_0304)));
+
+        // under major 0 every minor release may change the layout, so a 0.3 descriptor is not readable by a 0.7 parser
+        let v0_7 = DescriptorVersion::from(0x0000_0700);
+        assert!(v0_7.is_compatible_with(&DescriptorVersion::from(0x0000_0705)));
+        assert!(!v0_7.is_compatible_with(&DescriptorVersion::from(0x0000_0300)));
+        assert!(!v0_7.is_compatible_with(&DescriptorVersion::from(0x0000_0800)));
+        assert!(version < DescriptorVersion::from(0x0102_0305));
+        assert!(version > DescriptorVersion::from(0x00FF_FFFF));
+    }
+
+    #[test]
+    fn absurd_slot_counts_fail_cleanly() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + APP_IMAGE_DESCRIPTOR_SIZE;
+        let header = BootableRegionDescriptorHeader::new(u32::MAX, 0, BOOT_REGION_DESCRIPTOR_SIZE as u32);
+        let region: [u8; N] = build_region(header, &xip_apps::<1>());
+
+        // overflows on 16 and 32-bit targets, merely too big on 64-bit ones
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&region),
+            Err(ParseError::ArithmeticOverflow | ParseError::BufferTooSmall { .. })
+        ));
+        #[cfg(target_endian = "little")]
+        assert!(matches!(
+            parse_descriptors(&region),
+            Err(ParseError::ArithmeticOverflow | ParseError::BufferTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn validate_all_reports_every_error() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let mut apps = xip_apps::<3>();
+        apps[2].image_size_bytes = 0;
+        apps[2].recompute_crc();
+        let mut region = region_bytes::<N>(0, &apps);
+        // app_version of slot 0
+        region[BOOT_REGION_DESCRIPTOR_SIZE + 8] ^= 1;
+
+        let mut errors = [ParseError::InvalidSignature; 4];
+        assert_eq!(BootableRegionDescriptors::validate_all(&region, &mut errors), 2);
+        assert!(matches!(errors[0], ParseError::InvalidAppCrc { slot: 0, .. }));
+        assert!(matches!(errors[1], ParseError::EmptyImage { slot: 2 }));
+        assert!(BootableRegionDescriptors::from_bytes(&region).is_err());
+
+        // a bad header CRC doesn't hide the app descriptor errors, and the count covers errors that didn't fit
+        region[24] ^= 1;
+        let mut errors = [ParseError::InvalidSignature; 2];
+        assert_eq!(BootableRegionDescriptors::validate_all(&region, &mut errors), 3);
+        assert!(matches!(errors[0], ParseError::InvalidHeaderCrc { .. }));
+        assert!(matches!(errors[1], ParseError::InvalidAppCrc { slot: 0, .. }));
+
+        assert_eq!(
+            BootableRegionDescriptors::validate_all(&region_bytes::<N>(0, &xip_apps::<3>()), &mut errors),
+            0
+        );
+        let mut errors = [ParseError::InvalidSignature; 4];
+        assert_eq!(
+            BootableRegionDescriptors::validate_all(&region[..N - 1], &mut errors),
+            3
+        );
+        assert!(matches!(errors[2], ParseError::BufferTooSmall { needed: N, got } if got == N - 1));
+    }
+
+    #[test]
+    fn update_must_be_newer() {
+        let header = BootableRegionDescriptorHeader::new(3, 1, 0);
+        // app_version is slot + 1
+        let apps = Aligned(xip_apps::<3>());
+        let descriptors = region_from_parts(&header, &apps);
+
+        assert!(descriptors.is_update_newer(2).unwrap());
+        assert!(!descriptors.is_update_newer(1).unwrap());
+        assert!(!descriptors.is_update_newer(0).unwrap());
+        assert!(matches!(
+            descriptors.is_update_newer(3),
+            Err(ParseError::InvalidAppSlot)
+        ));
+    }
+
+    #[test]
+    fn reserved_padding_after_descriptors() {
+        const RESERVED: usize = 64;
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE + RESERVED;
+        let header = BootableRegionDescriptorHeader::new(2, 1, BOOT_REGION_DESCRIPTOR_SIZE as u32)
+            .with_reserved_bytes(RESERVED as u32);
+        let apps = xip_apps::<2>();
+        let mut region: [u8; N] = build_region(header, &apps);
+        // whatever future metadata lands in the reserved space is ignored
+        region[N - RESERVED..].fill(0xA5);
+
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!({ descriptors.header().reserved_bytes }, RESERVED as u32);
+        assert_eq!(descriptors.header().region_size().unwrap(), N);
+        assert_eq!(descriptors.get_active_slot(), apps[1]);
+
+        assert!(BootableRegionDescriptors::relocate(&mut region, 0, 0x100).is_ok());
+        assert!(region[N - RESERVED..].iter().all(|&byte| byte == 0xA5));
+    }
+
+    #[test]
+    fn from_address_bounded_stays_in_region() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + APP_IMAGE_DESCRIPTOR_SIZE;
+        // claims 4 slots but the region only has room for 1
+        let header = BootableRegionDescriptorHeader::new(4, 0, 0x1000_0000 + BOOT_REGION_DESCRIPTOR_SIZE as u32);
+        let region = Aligned::<[u8; N]>(build_region(header, &xip_apps::<1>()));
+        let address = region.ptr();
+
+        assert!(matches!(
+            BootableRegionDescriptors::from_address_bounded(address, N),
+            Err(ParseError::BufferTooSmall { needed, got: N })
+                if needed == BOOT_REGION_DESCRIPTOR_SIZE + 4 * APP_IMAGE_DESCRIPTOR_SIZE
+        ));
+        assert!(matches!(
+            BootableRegionDescriptors::from_address_bounded(address, BOOT_REGION_DESCRIPTOR_SIZE - 1),
+            Err(ParseError::BufferTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn boot_summaries() {
+        let xip = AppImageDescriptor::new_execute_in_place_image(
+            0,
+            1,
+            0,
+            APP_IMAGE_FLAG_NONE,
+            0x1000_0000,
+            0x8000,
+            0x1000_8000,
+        );
+        assert_eq!(
+            xip.boot_summary(),
+            BootSummary {
+                xip: true,
+                load_from: 0x1000_0000,
+                run_at: 0x1000_0000,
+                copy_len: 0,
+                crc_checked: true,
+            }
+        );
+
+        let ram = AppImageDescriptor::new_ram_image(
+            1,
+            1,
+            0,
+            APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+            0x1000_0000,
+            0x8000,
+            0x2000_0000,
+            0x1000_8000,
+        );
+        assert_eq!(
+            ram.boot_summary(),
+            BootSummary {
+                xip: false,
+                load_from: 0x1000_0000,
+                run_at: 0x2000_0000,
+                copy_len: 0x8000,
+                crc_checked: false,
+            }
+        );
+    }
+
+    #[test]
+    fn descriptors_before_header() {
+        const APPS: usize = 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        const N: usize = APPS + BOOT_REGION_DESCRIPTOR_SIZE;
+        let apps = xip_apps::<2>();
+        // descriptors at offset 0, header at the top of the buffer
+        let mut region = [0u8; N];
+        apps[0].write_to(&mut region).unwrap();
+        apps[1].write_to(&mut region[APP_IMAGE_DESCRIPTOR_SIZE..]).unwrap();
+        BootableRegionDescriptorHeader::new(2, 1, 0)
+            .write_to(&mut region[APPS..])
+            .unwrap();
 
-        this
+        let descriptors = BootableRegionDescriptors::from_bytes_at(&region, APPS).unwrap();
+        assert_eq!(descriptors.get_active_slot(), apps[1]);
+        assert_eq!(descriptors.get_app_at_slot(0).unwrap(), apps[0]);
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes_at(&region, N),
+            Err(ParseError::BufferTooSmall { .. })
+        ));
+
+        // the same layout mapped at 0x1000_0000, with the header read into its own buffer
+        let mut header = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        BootableRegionDescriptorHeader::new(2, 1, 0x1000_0000)
+            .write_to(&mut header)
+            .unwrap();
+        region[APPS..].copy_from_slice(&header);
+        let mapped = Aligned(region);
+        let descriptors = unsafe {
+            BootableRegionDescriptors::from_region(mapped.0[APPS..].as_ptr() as *const u32, 0x1000_0000 + APPS as u32)
+        }
+        .unwrap();
+        assert_eq!(descriptors.get_active_slot(), apps[1]);
+
+        let flash = Offset(region, 0x1000_0000);
+        let descriptors = BootableRegionDescriptors::from_reader(&flash, 0x1000_0000 + APPS as u32).unwrap();
+        assert_eq!(descriptors.get_app_at_slot(0).unwrap(), apps[0]);
+
+        /// Array mapped at a base address
+        struct Offset([u8; N], u32);
+        impl FlashRead for Offset {
+            type Error = ParseError;
+
+            fn read(&self, addr: u32, buf: &mut [u8]) -> Result<(), ParseError> {
+                self.0
+                    .read(addr.checked_sub(self.1).ok_or(ParseError::ArithmeticOverflow)?, buf)
+            }
+        }
     }
 
-    /// Return this struct's contents as a slice
-    pub const fn as_bytes(&self) -> &[u8] {
-        bytes_of(self)
+    #[test]
+    fn header_read_unchecked() {
+        let mut header = BootableRegionDescriptorHeader::new(3, 2, 0x1000_0024);
+        header.header_crc ^= 1;
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        header.write_to(&mut buf).unwrap();
+
+        assert!(matches!(
+            BootableRegionDescriptorHeader::from_bytes(&buf),
+            Err(ParseError::InvalidHeaderCrc { .. })
+        ));
+        let damaged = BootableRegionDescriptorHeader::read_unchecked(&buf).unwrap();
+        assert_eq!(damaged, header);
+        assert_eq!({ damaged.num_app_slots }, 3);
+        assert!(!damaged.is_crc_valid());
+
+        assert!(matches!(
+            BootableRegionDescriptorHeader::read_unchecked(&buf[1..]),
+            Err(ParseError::BufferTooSmall { .. })
+        ));
     }
 
-    /// Return the CRC32 checksum over the current contents of this struct
-    pub const fn compute_crc(&self) -> u32 {
-        let full_bytes = bytes_of(self);
+    #[test]
+    fn address_to_slot() {
+        let header = BootableRegionDescriptorHeader::new(2, 0, 0);
+        let apps = Aligned([
+            xip_apps::<1>()[0],
+            AppImageDescriptor::new_ram_image(
+                1,
+                1,
+                0,
+                APP_IMAGE_FLAG_NONE,
+                0x1010_0000,
+                0x1000,
+                0x2000_0000,
+                0x1010_1000,
+            ),
+        ]);
+        let descriptors = region_from_parts(&header, &apps);
 
-        // TODO - figure out a way to do a const slice semantically cleanly
-        // NOTE - as a const fn it's entirely possible this will not be allocated at all in a real program
-        let mut without_crc = [0u8; BOOT_REGION_DESCRIPTOR_SIZE - size_of::<u32>()];
-        let mut i = 0;
-        while i < without_crc.len() {
-            without_crc[i] = full_bytes[i];
-            i += 1;
+        assert_eq!(
+            descriptors.slot_for_address(0x1000_0FFF),
+            Some((0, AddressKind::Stored))
+        );
+        assert_eq!(
+            descriptors.slot_for_address(0x1010_0000),
+            Some((1, AddressKind::Stored))
+        );
+        assert_eq!(
+            descriptors.slot_for_address(0x2000_0800),
+            Some((1, AddressKind::Execution))
+        );
+        assert_eq!(descriptors.slot_for_address(0x1000_1000), None);
+        assert_eq!(descriptors.slot_for_address(0x2000_1000), None);
+    }
+
+    #[test]
+    fn slot_count_limited_by_default() {
+        let header = BootableRegionDescriptorHeader::new(1000, 0, 0x1000_0000);
+        assert!(matches!(
+            BootableRegionDescriptors::from_address(Aligned(header).ptr()),
+            Err(ParseError::TooManySlots {
+                found: 1000,
+                max: MAX_REASONABLE_SLOTS
+            })
+        ));
+
+        let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        header.write_to(&mut buf).unwrap();
+        assert!(matches!(
+            BootableRegionDescriptors::from_reader(&buf, 0),
+            Err(ParseError::TooManySlots { found: 1000, .. })
+        ));
+
+        assert!(BootableRegionDescriptorHeader::new(MAX_REASONABLE_SLOTS, 0, 0)
+            .validate_slot_count(MAX_REASONABLE_SLOTS)
+            .is_ok());
+    }
+
+    #[test]
+    fn write_descriptors_to_region() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let apps = xip_apps::<2>();
+        let header = BootableRegionDescriptorHeader::new(2, 1, BOOT_REGION_DESCRIPTOR_SIZE as u32);
+
+        let mut region = [0u8; N];
+        header.write_to(&mut region).unwrap();
+        for (slot, app) in apps.iter().enumerate() {
+            app.write_to_region(&mut region, &header, slot as u32).unwrap();
         }
+        assert_eq!(region, region_bytes::<N>(1, &apps));
 
-        Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(&without_crc)
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!(descriptors.get_app_at_slot(0).unwrap(), apps[0]);
+        assert_eq!(descriptors.get_active_slot(), apps[1]);
+
+        assert!(matches!(
+            apps[0].write_to_region(&mut region, &header, 2),
+            Err(ParseError::InvalidAppSlot)
+        ));
+        assert!(matches!(
+            apps[1].write_to_region(&mut region[..N - 1], &header, 1),
+            Err(ParseError::BufferTooSmall { needed: N, got }) if got == N - 1
+        ));
     }
 
-    /// Check if the header_crc value matches the current computed CRC32 checksum
-    pub const fn is_crc_valid(&self) -> bool {
-        self.header_crc == self.compute_crc()
+    #[test]
+    fn content_crc_ignores_version() {
+        let app = xip_apps::<1>()[0];
+        let mut older = AppImageDescriptor {
+            descriptor_version: DescriptorVersion {
+                major: 0,
+                minor: 3,
+                patch: 0,
+            }
+            .to_u32(),
+            ..app
+        };
+        older.recompute_crc();
+
+        assert_ne!(older.as_bytes(), app.as_bytes());
+        assert_ne!({ older.descriptor_crc }, { app.descriptor_crc });
+        assert_eq!(older.content_crc(), app.content_crc());
+        assert_ne!(app.content_crc(), app.compute_crc());
+
+        let other = AppImageDescriptor { app_version: 7, ..app };
+        assert_ne!(other.content_crc(), app.content_crc());
     }
-}
 
-impl AppImageDescriptor {
-    /// Attempt to read app_slot AppImageDescriptor from app_descriptors_address_start
-    pub fn from_region(
-        app_descriptors_address_start: *const u32,
-        app_slot: u32,
-    ) -> Result<AppImageDescriptor, ParseError> {
-        AppImageDescriptor::from_address(unsafe {
-            (app_descriptors_address_start as *const u8).add((app_slot as usize) * APP_IMAGE_DESCRIPTOR_SIZE)
-                as *const u32
-        })
+    #[test]
+    fn stream_slots_from_reader() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 4 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let apps = xip_apps::<4>();
+        let mut region = region_bytes::<N>(0, &apps);
+        let header = BootableRegionDescriptorHeader::from_bytes(&region).unwrap();
+
+        let flash = region;
+        let mut slots = BootableRegionDescriptors::slot_reader(&flash, &header);
+        for app in apps {
+            assert_eq!(slots.next().unwrap().unwrap(), app);
+        }
+        assert!(slots.next().is_none());
+
+        // a corrupt slot is reported in place and the rest still stream
+        let offset = header.descriptor_offset(2).unwrap();
+        region[offset + 8] ^= 1;
+        let flash = region;
+        let mut slots = BootableRegionDescriptors::slot_reader(&flash, &header).skip(1);
+        assert!(slots.next().unwrap().is_ok());
+        assert!(matches!(
+            slots.next().unwrap(),
+            Err(ParseError::InvalidAppCrc { slot: 2, .. })
+        ));
+        assert_eq!(slots.next().unwrap().unwrap(), apps[3]);
     }
 
-    /// Generate a non-copied (XIP: execute in place) app image descriptor with the given parameters
-    pub const fn new_execute_in_place_image(
-        slot: u32,
-        app_version: u32,
-        security_version: u32,
-        flags: u32,
-        stored_address: u32,
-        image_size_bytes: u32,
-        stored_crc_address: u32,
-    ) -> AppImageDescriptor {
-        let mut app_image_descriptor = Self {
-            descriptor_version: DESCRIPTOR_VERSION,
-            app_slot_number: slot,
-            app_version,
-            security_version,
-            flags,
-            stored_address,
-            image_size_bytes,
-            stored_crc_address,
-            execution_address: stored_address,
-            execution_copy_size_bytes: 0,
-            descriptor_crc: 0,
+    #[test]
+    fn stored_crc_accessors() {
+        let app = xip_apps::<1>()[0];
+        assert_eq!(app.stored_crc(), app.compute_crc());
+
+        let header = BootableRegionDescriptorHeader::new(1, 0, BOOT_REGION_DESCRIPTOR_SIZE as u32);
+        assert_eq!(header.stored_crc(), header.compute_crc());
+
+        let mut stale = app;
+        stale.app_version += 1;
+        assert!(matches!(
+            AppImageDescriptor::from_bytes(stale.as_bytes()),
+            Err(ParseError::InvalidAppCrc { found, expected, .. })
+                if found == app.stored_crc() && expected == stale.compute_crc()
+        ));
+    }
+
+    #[test]
+    fn copy_size_consistency() {
+        let copied = |copy_size| AppImageDescriptor {
+            execution_copy_size_bytes: copy_size,
+            ..AppImageDescriptor::new_ram_image(
+                2,
+                1,
+                0,
+                APP_IMAGE_FLAG_NONE,
+                0x1000_0000,
+                0x1000,
+                0x2000_0000,
+                0x1000_1000,
+            )
         };
 
-        app_image_descriptor.descriptor_crc = app_image_descriptor.compute_crc();
+        assert!(copied(0x1000).copy_is_consistent().is_ok());
+        assert!(matches!(
+            copied(0).copy_is_consistent(),
+            Err(ParseError::InconsistentCopySize { slot: 2 })
+        ));
+        assert!(matches!(
+            copied(0x800).copy_is_consistent(),
+            Err(ParseError::InconsistentCopySize { slot: 2 })
+        ));
+        assert!(copied(0x1100).copy_is_consistent().is_err());
 
-        app_image_descriptor
+        // padding up to the tolerance is allowed, a short copy never is
+        assert!(copied(0x1100).copy_is_consistent_within(0x100).is_ok());
+        assert!(copied(0x1101).copy_is_consistent_within(0x100).is_err());
+        assert!(copied(0xFFF).copy_is_consistent_within(0x100).is_err());
+
+        // execute in place images don't copy anything
+        assert!(xip_apps::<1>()[0].copy_is_consistent().is_ok());
     }
 
-    #[allow(clippy::too_many_arguments)]
-    /// Generate a copied to RAM app image descriptor with given parameters
-    pub const fn new_ram_image(
-        slot: u32,
-        app_version: u32,
-        security_version: u32,
-        flags: u32,
-        flash_address: u32,
-        image_size_bytes: u32,
-        ram_address: u32,
-        stored_crc_address: u32,
-    ) -> Self {
-        let mut app_image_descriptor = Self {
-            descriptor_version: DESCRIPTOR_VERSION,
-            app_slot_number: slot,
-            app_version,
-            security_version,
-            flags: flags | APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS,
-            stored_address: flash_address,
-            image_size_bytes,
-            stored_crc_address,
-            execution_address: ram_address,
-            execution_copy_size_bytes: image_size_bytes,
-            descriptor_crc: 0,
+    #[test]
+    fn golden_single_slot_region() {
+        let app = xip_apps::<4>()[3];
+        let region = BootableRegionDescriptors::single_slot_region(app, BOOT_REGION_DESCRIPTOR_SIZE as u32);
+
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!({ descriptors.header().num_app_slots }, 1);
+        assert_eq!(descriptors.active_slot_index(), 0);
+
+        // the descriptor lands in slot 0 with its CRC recomputed
+        let mut expected = AppImageDescriptor {
+            app_slot_number: 0,
+            ..app
         };
+        expected.recompute_crc();
+        assert_eq!(descriptors.get_active_slot(), expected);
+    }
 
-        app_image_descriptor.descriptor_crc = app_image_descriptor.compute_crc();
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not 4 byte aligned")]
+    fn misaligned_address_asserts() {
+        let region = Aligned([0u8; BOOT_REGION_DESCRIPTOR_SIZE + 1]);
+        let misaligned = region.0[1..].as_ptr() as *const u32;
+        let _ = BootableRegionDescriptors::from_address(misaligned);
+    }
 
-        app_image_descriptor
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not 4 byte aligned")]
+    fn misaligned_app_descriptors_assert() {
+        let header = BootableRegionDescriptorHeader::new(1, 0, 0);
+        let apps = Aligned([0u8; APP_IMAGE_DESCRIPTOR_SIZE + 1]);
+        let misaligned = apps.0[1..].as_ptr() as *const u32;
+        let _ = BootableRegionDescriptors::from_header_and_app_regions(Aligned(header).ptr(), misaligned);
     }
 
-    /// Attempt to interpret address memory contents as an AppImageDescriptor
-    pub fn from_address(address: *const u32) -> Result<AppImageDescriptor, ParseError> {
-        let unvalidated = unsafe { *(address as *const AppImageDescriptor) };
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not 4 byte aligned")]
+    fn misaligned_app_region_asserts() {
+        let apps = Aligned([0u8; APP_IMAGE_DESCRIPTOR_SIZE + 1]);
+        let misaligned = apps.0[1..].as_ptr() as *const u32;
+        let _ = AppImageDescriptor::from_region(misaligned, 0);
+    }
 
-        if !unvalidated.is_crc_valid() {
-            Err(ParseError::InvalidAppCrc {
-                address,
-                found: unvalidated.descriptor_crc,
-                expected: unvalidated.compute_crc(),
+    #[test]
+    fn image_name_round_trip() {
+        let app = AppImageDescriptor::builder()
+            .slot(1)
+            .stored(0x1000_0000, 0x1000)
+            .name("ec-main-1.2")
+            .build();
+        assert_eq!(app.name_str(), "ec-main-1.2");
+        assert!(app.is_crc_valid());
+
+        let mut buf = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        app.write_to(&mut buf).unwrap();
+        assert_eq!(AppImageDescriptor::from_bytes(&buf).unwrap().name_str(), "ec-main-1.2");
+
+        // the name is CRC covered
+        buf[core::mem::offset_of!(AppImageDescriptor, name)] ^= 1;
+        assert!(matches!(
+            AppImageDescriptor::from_bytes(&buf),
+            Err(ParseError::InvalidAppCrc { .. })
+        ));
+
+        // long names are truncated on a character boundary, invalid UTF-8 is cut at the first bad byte
+        let long = AppImageDescriptor::builder().name("recovery-image-é").build();
+        assert_eq!(long.name_str(), "recovery-image-");
+        let mut invalid = long;
+        invalid.name[4] = 0xFF;
+        assert_eq!(invalid.name_str(), "reco");
+        assert_eq!(AppImageDescriptor::default().name_str(), "");
+    }
+
+    #[test]
+    fn image_integrity_types() {
+        let image = [0x5Au8; 0x100];
+        let crc = Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(&image);
+        let crc32 = AppImageDescriptor::builder()
+            .slot(1)
+            .stored(0, image.len() as u32)
+            .integrity_type(INTEGRITY_CRC32)
+            .build();
+        assert!(crc32.validate_image(&image, crc).is_ok());
+        assert!(matches!(
+            crc32.validate_image(&image, !crc),
+            Err(ParseError::InvalidImageCrc { .. })
+        ));
+
+        // the descriptor itself is still CRC32 checked whatever the image integrity type
+        let sha256 = AppImageDescriptor::builder()
+            .slot(1)
+            .stored(0, image.len() as u32)
+            .integrity_type(INTEGRITY_SHA256)
+            .build();
+        assert!(sha256.is_crc_valid());
+        assert!(matches!(
+            sha256.validate_image(&image, crc),
+            Err(ParseError::UnsupportedIntegrityType {
+                slot: 1,
+                integrity_type: INTEGRITY_SHA256
             })
-        } else {
-            Ok(unvalidated)
+        ));
+
+        // the streaming and chunked paths share the check
+        assert!(matches!(
+            sha256.verify_image_crc(&image, crc),
+            Err(ParseError::UnsupportedIntegrityType { .. })
+        ));
+        assert!(matches!(
+            sha256.verify_image_segments([&image[..0x80], &image[0x80..]], crc),
+            Err(ParseError::UnsupportedIntegrityType { .. })
+        ));
+        let mut digest = sha256.image_digest();
+        digest.update(&image);
+        assert!(matches!(
+            sha256.finish_image_digest(digest, crc),
+            Err(ParseError::UnsupportedIntegrityType { .. })
+        ));
+
+        // skipping the check skips it for any integrity type
+        let skipped = AppImageDescriptor {
+            flags: APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
+            ..sha256
+        };
+        assert!(skipped.validate_image(&image, 0).is_ok());
+    }
+
+    #[test]
+    fn persist_active_slot() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let apps = xip_apps::<3>();
+        let mut region = region_bytes::<N>(0, &apps);
+
+        let header = |active, sequence| {
+            BootableRegionDescriptorHeader::new(3, active, BOOT_REGION_DESCRIPTOR_SIZE as u32).with_sequence(sequence)
+        };
+
+        // every write of the header bumps sequence, the app descriptors are left alone
+        assert!(BootableRegionDescriptors::set_active_slot_in_buffer(&mut region, 2).is_ok());
+        assert_eq!(
+            BootableRegionDescriptorHeader::from_bytes(&region).unwrap(),
+            header(2, 1)
+        );
+        assert_eq!(
+            region[BOOT_REGION_DESCRIPTOR_SIZE..],
+            region_bytes::<N>(2, &apps)[BOOT_REGION_DESCRIPTOR_SIZE..]
+        );
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!(descriptors.get_active_slot(), apps[2]);
+
+        // only the header is needed
+        assert!(
+            BootableRegionDescriptors::set_active_slot_in_buffer(&mut region[..BOOT_REGION_DESCRIPTOR_SIZE], 1).is_ok()
+        );
+        assert_eq!(
+            BootableRegionDescriptorHeader::from_bytes(&region).unwrap(),
+            header(1, 2)
+        );
+
+        assert!(matches!(
+            BootableRegionDescriptors::set_active_slot_in_buffer(&mut region, 3),
+            Err(ParseError::InvalidAppSlot)
+        ));
+        region[4] ^= 1;
+        assert!(matches!(
+            BootableRegionDescriptors::set_active_slot_in_buffer(&mut region, 0),
+            Err(ParseError::InvalidHeaderCrc { .. })
+        ));
+    }
+
+    #[test]
+    fn explicit_endianness_round_trip() {
+        let header = BootableRegionDescriptorHeader::new(3, 1, 0x1000_0024);
+        let app = xip_apps::<2>()[1];
+
+        for endian in [Endianness::Little, Endianness::Big] {
+            let mut buf = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+            header.write_to_endian(&mut buf, endian).unwrap();
+            assert_eq!(
+                BootableRegionDescriptorHeader::from_bytes_endian(&buf, endian).unwrap(),
+                header
+            );
+
+            let mut buf = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+            app.write_to_endian(&mut buf, endian).unwrap();
+            assert_eq!(AppImageDescriptor::from_bytes_endian(&buf, endian).unwrap(), app);
         }
+
+        // the on-flash byte order is little endian, whatever the host
+        let mut le = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        app.write_to_endian(&mut le, Endianness::ON_FLASH).unwrap();
+        let mut be = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        app.write_to_endian(&mut be, Endianness::Big).unwrap();
+        assert_eq!(le[4..8], 1u32.to_le_bytes());
+        assert_eq!(be[4..8], 1u32.to_be_bytes());
+        assert!(matches!(
+            AppImageDescriptor::from_bytes(&be),
+            Err(ParseError::InvalidAppCrc { .. })
+        ));
+
+        let mut native = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        app.write_to_endian(&mut native, Endianness::NATIVE).unwrap();
+        assert_eq!(native, app.as_bytes());
+        assert_eq!(
+            AppImageDescriptor::from_bytes_endian(app.as_bytes(), Endianness::NATIVE).unwrap(),
+            app
+        );
     }
 
-    /// Return this structure as a slice
-    pub const fn as_bytes(&self) -> &[u8] {
-        bytes_of(self)
+    #[test]
+    fn build_with_image_crc() {
+        let image: [u8; 0x80] = core::array::from_fn(|i| i as u8);
+        let builder = AppImageDescriptor::builder()
+            .slot(1)
+            .stored(0x1000_0000, 0x10)
+            .stored_crc_address(0x1000_0080);
+        let (descriptor, crc) = AppImageDescriptor::with_computed_image_crc(builder, &image).unwrap();
+
+        assert_eq!({ descriptor.image_size_bytes }, 0x80);
+        assert!(descriptor.is_crc_valid());
+        assert!(descriptor.verify_image_crc(&image, crc).is_ok());
+        assert!(descriptor.verify_image_crc(&image, crc ^ 1).is_err());
+
+        // the copy size follows the image size rather than whatever the builder was given
+        let builder = AppImageDescriptor::builder()
+            .slot(1)
+            .stored(0x1000_0000, 0x10)
+            .execution(0x2000_0000, 0x10);
+        let (descriptor, _) = AppImageDescriptor::with_computed_image_crc(builder, &image).unwrap();
+        assert_eq!({ descriptor.execution_copy_size_bytes }, 0x80);
+        assert!(descriptor.copy_is_consistent().is_ok());
+
+        // only a CRC32 can be computed, a descriptor expecting a SHA-256 digest is refused
+        let builder = AppImageDescriptor::builder()
+            .slot(1)
+            .stored(0x1000_0000, 0x10)
+            .integrity_type(INTEGRITY_SHA256);
+        assert!(matches!(
+            AppImageDescriptor::with_computed_image_crc(builder, &image),
+            Err(ParseError::UnsupportedIntegrityType {
+                slot: 1,
+                integrity_type: INTEGRITY_SHA256
+            })
+        ));
     }
 
-    /// Compute the CRC32 checksum of this structures current contents
-    pub const fn compute_crc(&self) -> u32 {
-        let full_bytes = bytes_of(self);
+    #[test]
+    fn best_slot_by_security_then_app_version() {
+        let mut apps = Aligned(xip_apps::<4>());
+        let versions = [(1, 9), (2, 3), (2, 5), (2, 5)];
+        for (app, (security_version, app_version)) in apps.iter_mut().zip(versions) {
+            app.security_version = security_version;
+            app.app_version = app_version;
+            app.recompute_crc();
+        }
 
-        // TODO - figure out a way to do a const slice semantically cleanly
-        // NOTE - as a const fn it's entirely possible this will not be allocated at all in a real program
-        let mut without_crc = [0u8; APP_IMAGE_DESCRIPTOR_SIZE - size_of::<u32>()];
-        let mut i = 0;
-        while i < without_crc.len() {
-            without_crc[i] = full_bytes[i];
-            i += 1;
+        // security_version wins over a higher app_version, app_version breaks the tie, the lowest slot breaks the rest
+        let header = BootableRegionDescriptorHeader::new(4, 0, 0);
+        let descriptors = region_from_parts(&header, &apps);
+        assert_eq!({ descriptors.best_slot().unwrap().app_slot_number }, 2);
+        assert_eq!(
+            { descriptors.highest_security_version_slot().unwrap().app_slot_number },
+            1
+        );
+        assert_eq!({ descriptors.highest_version_slot().unwrap().app_slot_number }, 0);
+
+        assert!(SlotCandidate(apps[1]) > SlotCandidate(apps[0]));
+        assert!(SlotCandidate(apps[2]) > SlotCandidate(apps[1]));
+        assert_eq!(SlotCandidate(apps[2]), SlotCandidate(apps[3]));
+        assert_eq!(apps.map(SlotCandidate).into_iter().max().unwrap().key(), (2, 5));
+    }
+
+    #[test]
+    fn init_empty_region() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let mut region = [0xFFu8; N];
+        BootableRegionDescriptors::init_region(&mut region, 3, BOOT_REGION_DESCRIPTOR_SIZE as u32).unwrap();
+
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!({ descriptors.header().num_app_slots }, 3);
+        assert!(descriptors.iter().all(|app| app.is_ok_and(|app| app.is_blank())));
+
+        // nothing to boot until a slot is populated and made active
+        assert!(matches!(
+            descriptors.select_boot_slot(0),
+            Err(ParseError::EmptyImage { slot: 0 })
+        ));
+        assert!(matches!(
+            descriptors.boot_or_recovery(),
+            Err(ParseError::EmptyImage { slot: 0 })
+        ));
+
+        // a region too small for every slot is left untouched
+        let mut small = [0xFFu8; N - 1];
+        assert!(matches!(
+            BootableRegionDescriptors::init_region(&mut small, 3, BOOT_REGION_DESCRIPTOR_SIZE as u32),
+            Err(ParseError::BufferTooSmall { needed: N, .. })
+        ));
+        assert!(small.iter().all(|&byte| byte == 0xFF));
+
+        assert!(matches!(
+            BootableRegionDescriptors::init_region(&mut region, 0, BOOT_REGION_DESCRIPTOR_SIZE as u32),
+            Err(ParseError::InvalidSlotCount)
+        ));
+        assert!(matches!(
+            BootableRegionDescriptors::init_region(&mut region, 1, 0),
+            Err(ParseError::DescriptorBaseOverlapsHeader)
+        ));
+    }
+
+    #[test]
+    fn erased_region_detected() {
+        for erased in [0xFFu8, 0x00] {
+            let region = [erased; BOOT_REGION_DESCRIPTOR_SIZE + APP_IMAGE_DESCRIPTOR_SIZE];
+            assert!(matches!(
+                BootableRegionDescriptorHeader::from_bytes(&region),
+                Err(ParseError::ErasedRegion)
+            ));
+            assert!(matches!(
+                BootableRegionDescriptors::from_bytes(&region),
+                Err(ParseError::ErasedRegion)
+            ));
         }
 
-        Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(&without_crc)
+        // a single programmed byte makes it corrupt rather than erased
+        let mut region = [0xFFu8; BOOT_REGION_DESCRIPTOR_SIZE];
+        region[BOOT_REGION_DESCRIPTOR_SIZE - 1] = 0;
+        assert!(matches!(
+            BootableRegionDescriptorHeader::from_bytes(&region),
+            Err(ParseError::InvalidSignature)
+        ));
     }
 
-    /// Check this structure's stored descriptor_crc against computed CRC32 checksum of its current contents
-    pub const fn is_crc_valid(&self) -> bool {
-        self.descriptor_crc == self.compute_crc()
+    #[test]
+    fn redundant_higher_sequence_wins() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let base = BOOT_REGION_DESCRIPTOR_SIZE as u32;
+        let older = build_region::<N>(
+            BootableRegionDescriptorHeader::new(2, 0, base).with_sequence(4),
+            &xip_apps::<2>(),
+        );
+        let newer = build_region::<N>(
+            BootableRegionDescriptorHeader::new(2, 1, base).with_sequence(5),
+            &xip_apps::<2>(),
+        );
+
+        let descriptors = BootableRegionDescriptors::from_redundant(&older, &newer).unwrap();
+        assert_eq!({ descriptors.header().sequence }, 5);
+        assert_eq!(descriptors.active_slot_index(), 1);
+
+        let descriptors = BootableRegionDescriptors::from_redundant(&newer, &older).unwrap();
+        assert_eq!({ descriptors.header().sequence }, 5);
+
+        // the primary wins a tie
+        let descriptors = BootableRegionDescriptors::from_redundant(&older, &older).unwrap();
+        assert_eq!(descriptors.active_slot_index(), 0);
+
+        // sequence keeps ordering across wrapping from u32::MAX to 0
+        let wrapped = build_region::<N>(
+            BootableRegionDescriptorHeader::new(2, 1, base).with_sequence(0),
+            &xip_apps::<2>(),
+        );
+        let before_wrap = build_region::<N>(
+            BootableRegionDescriptorHeader::new(2, 0, base).with_sequence(u32::MAX),
+            &xip_apps::<2>(),
+        );
+        let descriptors = BootableRegionDescriptors::from_redundant(&before_wrap, &wrapped).unwrap();
+        assert_eq!({ descriptors.header().sequence }, 0);
+        let descriptors = BootableRegionDescriptors::from_redundant(&wrapped, &before_wrap).unwrap();
+        assert_eq!({ descriptors.header().sequence }, 0);
+
+        // rewriting the older copy in place makes it the newer one
+        let mut rewritten = older;
+        assert!(BootableRegionDescriptors::set_active_slot_in_buffer(&mut rewritten, 0).is_ok());
+        assert!(BootableRegionDescriptors::set_active_slot_in_buffer(&mut rewritten, 0).is_ok());
+        let descriptors = BootableRegionDescriptors::from_redundant(&newer, &rewritten).unwrap();
+        assert_eq!({ descriptors.header().sequence }, 6);
+        assert_eq!(descriptors.active_slot_index(), 0);
+
+        // repairing the CRCs of a stale copy doesn't make it win over the newer one
+        let mut repaired = older;
+        repaired[core::mem::offset_of!(BootableRegionDescriptorHeader, header_crc)] ^= 1;
+        assert!(recompute_header_crc(&mut repaired).is_ok());
+        assert!(recompute_all_crcs(&mut repaired).is_ok());
+        let descriptors = BootableRegionDescriptors::from_redundant(&newer, &repaired).unwrap();
+        assert_eq!({ descriptors.header().sequence }, 5);
     }
-}
 
-#[cfg(test)]
-mod unit_tests {
+    #[test]
+    fn redundant_falls_back_to_valid_copy() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let base = BOOT_REGION_DESCRIPTOR_SIZE as u32;
+        let backup = build_region::<N>(
+            BootableRegionDescriptorHeader::new(2, 0, base).with_sequence(4),
+            &xip_apps::<2>(),
+        );
+
+        // a torn write of the newer primary leaves its header CRC bad
+        let mut primary = build_region::<N>(
+            BootableRegionDescriptorHeader::new(2, 1, base).with_sequence(5),
+            &xip_apps::<2>(),
+        );
+        primary[core::mem::offset_of!(BootableRegionDescriptorHeader, active_app_slot)] ^= 1;
+        let descriptors = BootableRegionDescriptors::from_redundant(&primary, &backup).unwrap();
+        assert_eq!({ descriptors.header().sequence }, 4);
+        assert_eq!(descriptors.active_slot_index(), 0);
+
+        let erased = [0xFFu8; N];
+        let descriptors = BootableRegionDescriptors::from_redundant(&erased, &backup).unwrap();
+        assert_eq!({ descriptors.header().sequence }, 4);
+
+        // with neither copy valid, the primary's error is reported. A bad app descriptor invalidates its whole copy.
+        let mut backup_bad_app = backup;
+        backup_bad_app[BOOT_REGION_DESCRIPTOR_SIZE + 8] ^= 1;
+        assert!(matches!(
+            BootableRegionDescriptors::from_redundant(&primary, &backup_bad_app),
+            Err(ParseError::InvalidHeaderCrc { .. })
+        ));
+        assert!(matches!(
+            BootableRegionDescriptors::from_redundant(&erased, &primary),
+            Err(ParseError::ErasedRegion)
+        ));
+    }
 
     #[test]
-    fn test_ram_descriptor_gen() {
-        use super::*;
+    fn recompute_header_crc_repairs_header() {
+        const N: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+        let mut region = region_bytes::<N>(0, &xip_apps::<2>());
+        let untouched = region;
 
-        let app_image_descriptor = AppImageDescriptor::new_ram_image(
-            0,
-            0,
-            0,
-            APP_IMAGE_FLAG_NONE | APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK,
-            0,
-            0,
-            0,
-            0,
+        let crc = core::mem::offset_of!(BootableRegionDescriptorHeader, header_crc);
+        region[crc] ^= 1;
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&region),
+            Err(ParseError::InvalidHeaderCrc { .. })
+        ));
+        assert!(recompute_header_crc(&mut region).is_ok());
+        assert_eq!(region, untouched);
+
+        // only header_crc is recomputed, so repeating it changes nothing
+        assert!(recompute_header_crc(&mut region).is_ok());
+        assert_eq!(region, untouched);
+
+        // an edited active slot, with only the header in the buffer
+        let active = core::mem::offset_of!(BootableRegionDescriptorHeader, active_app_slot);
+        region[active..active + 4].copy_from_slice(&1u32.to_le_bytes());
+        assert!(recompute_header_crc(&mut region[..BOOT_REGION_DESCRIPTOR_SIZE]).is_ok());
+        assert_eq!(
+            BootableRegionDescriptors::from_bytes(&region)
+                .unwrap()
+                .active_slot_index(),
+            1
         );
 
-        assert_ne!(app_image_descriptor.flags & APP_IMAGE_FLAG_COPY_TO_EXECUTION_ADDRESS, 0);
-        let embedded_crc = app_image_descriptor.descriptor_crc;
-        let computed_crc = app_image_descriptor.compute_crc();
-        assert_eq!(embedded_crc, computed_crc);
+        assert!(matches!(
+            recompute_header_crc(&mut region[..BOOT_REGION_DESCRIPTOR_SIZE - 1]),
+            Err(ParseError::BufferTooSmall { .. })
+        ));
+        region[0] = 0;
+        assert!(matches!(
+            recompute_header_crc(&mut region),
+            Err(ParseError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn validate_owned_header() {
+        let header = BootableRegionDescriptorHeader::new(2, 1, 0x1000);
+        assert!(header.validate().is_ok());
+
+        let mut stale = header;
+        stale.active_app_slot = 0;
+        assert!(matches!(
+            stale.validate(),
+            Err(ParseError::InvalidHeaderCrc { found, .. }) if found == header.header_crc
+        ));
+
+        let edited = |edit: fn(&mut BootableRegionDescriptorHeader)| {
+            let mut header = header;
+            edit(&mut header);
+            header.recompute_crc();
+            header.validate()
+        };
+        assert!(matches!(edited(|h| h.signature = 0), Err(ParseError::InvalidSignature)));
+        assert!(matches!(
+            edited(|h| h.descriptor_header_size_bytes += 4),
+            Err(ParseError::SizeMismatch {
+                field: SizeField::DescriptorHeaderSize,
+                ..
+            })
+        ));
+        assert!(matches!(
+            edited(|h| h.app_descriptor_size_bytes += 4),
+            Err(ParseError::SizeMismatch {
+                field: SizeField::AppDescriptorSize,
+                ..
+            })
+        ));
+        assert!(matches!(
+            edited(|h| h.num_app_slots = 0),
+            Err(ParseError::InvalidSlotCount)
+        ));
+        assert!(matches!(
+            edited(|h| h.active_app_slot = 2),
+            Err(ParseError::InvalidAppSlot)
+        ));
+
+        // from_bytes agrees with validate
+        let mut bad = header;
+        bad.active_app_slot = 2;
+        bad.recompute_crc();
+        assert!(matches!(
+            BootableRegionDescriptorHeader::from_bytes(bad.as_bytes()),
+            Err(ParseError::InvalidAppSlot)
+        ));
+    }
+
+    #[test]
+    fn validate_owned_app() {
+        let app = AppImageDescriptor::new_execute_in_place_image(1, 1, 0, APP_IMAGE_FLAG_NONE, 0x1000, 0x100, 0x1100);
+        assert!(app.validate().is_ok());
+        // a blank descriptor is a valid slot that isn't populated yet, booting one is rejected by select_boot_slot
+        assert!(AppImageDescriptor::default().validate().is_ok());
+
+        let mut stale = app;
+        stale.app_version = 2;
+        assert!(matches!(
+            stale.validate(),
+            Err(ParseError::InvalidAppCrc { slot: 1, address: 0, found, .. }) if found == app.descriptor_crc
+        ));
+
+        let edited = |edit: fn(&mut AppImageDescriptor)| {
+            let mut app = app;
+            edit(&mut app);
+            app.recompute_crc();
+            app.validate()
+        };
+        assert!(matches!(
+            edited(|a| a.stored_address = u32::MAX),
+            Err(ParseError::AddressOverflow { slot: 1 })
+        ));
+        assert!(matches!(
+            edited(|a| {
+                a.execution_address = u32::MAX;
+                a.execution_copy_size_bytes = 0x100;
+            }),
+            Err(ParseError::AddressOverflow { slot: 1 })
+        ));
+        assert!(matches!(
+            edited(|a| a.image_size_bytes = 0),
+            Err(ParseError::EmptyImage { slot: 1 })
+        ));
+        assert!(edited(|a| {
+            a.image_size_bytes = 0;
+            a.flags = APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK;
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn reader_failure_after_construction_does_not_panic() {
+        struct Driver {
+            flash: [u8; BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE],
+            fail: core::cell::Cell<bool>,
+        }
+
+        impl FlashRead for Driver {
+            type Error = ();
+
+            fn read(&self, addr: u32, buf: &mut [u8]) -> Result<(), ()> {
+                if self.fail.get() {
+                    return Err(());
+                }
+                buf.copy_from_slice(&self.flash[addr as usize..addr as usize + buf.len()]);
+                Ok(())
+            }
+        }
+
+        let driver = Driver {
+            flash: region_bytes(1, &xip_apps::<2>()),
+            fail: core::cell::Cell::new(false),
+        };
+        let descriptors = BootableRegionDescriptors::from_reader(&driver, 0).unwrap();
+
+        // the driver starts failing, e.g. after the flash controller is reconfigured
+        driver.fail.set(true);
+        assert_eq!(descriptors.get_active_slot(), xip_apps::<2>()[1]);
+        assert!(matches!(
+            descriptors.active_slot_rechecked(),
+            Err(ParseError::FlashReadFailed { .. })
+        ));
+        assert!(descriptors
+            .iter()
+            .all(|app| matches!(app, Err(ParseError::FlashReadFailed { .. }))));
+        assert!(matches!(
+            descriptors.highest_version_slot(),
+            Err(ParseError::FlashReadFailed { .. })
+        ));
+        assert!(matches!(
+            descriptors.best_slot(),
+            Err(ParseError::FlashReadFailed { .. })
+        ));
+        assert!(matches!(
+            descriptors.validate_strict(),
+            Err(ParseError::FlashReadFailed { .. })
+        ));
+        assert!(matches!(
+            descriptors.descriptor_versions_consistent(),
+            Err(ParseError::FlashReadFailed { .. })
+        ));
+        assert!(descriptors.recovery_slot().is_none());
+        assert!(descriptors.slot_for_address(0x1000_0000).is_none());
+        assert!(descriptors.select_boot_slot(0).is_ok());
     }
 
     #[test]
     fn bootable_region_descriptors_init() {}
 
     #[test]
-    fn bootable_region_descriptors_load() {}
+    fn bootable_region_descriptors_load() {
+        let apps = xip_apps::<3>();
+        let region: [u8; BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE] = region_bytes(1, &apps);
+
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!(descriptors.get_active_slot().as_bytes(), apps[1].as_bytes());
+        for (i, app) in descriptors.iter().enumerate() {
+            assert_eq!(app.unwrap().as_bytes(), apps[i].as_bytes());
+        }
+
+        // the same region works from an unaligned buffer
+        let mut unaligned = [0u8; BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE + 1];
+        unaligned[1..].copy_from_slice(&region);
+        let descriptors = BootableRegionDescriptors::from_bytes(&unaligned[1..]).unwrap();
+        assert_eq!(descriptors.get_active_slot().as_bytes(), apps[1].as_bytes());
+    }
 
     #[test]
-    fn bootable_region_descriptors_catch_garbage() {}
+    fn bootable_region_descriptors_catch_garbage() {
+        let apps = xip_apps::<3>();
+        let region: [u8; BOOT_REGION_DESCRIPTOR_SIZE + 3 * APP_IMAGE_DESCRIPTOR_SIZE] = region_bytes(1, &apps);
+
+        // descriptors past the end of the buffer are never read
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&region[..region.len() - 1]),
+            Err(ParseError::BufferTooSmall { needed, got }) if needed == region.len() && got == region.len() - 1
+        ));
+
+        // corrupt app descriptor
+        let mut corrupt = region;
+        corrupt[BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE] ^= 0xFF;
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&corrupt),
+            Err(ParseError::InvalidAppCrc { slot: 2, .. })
+        ));
+
+        // corrupt header
+        let mut corrupt = region;
+        corrupt[BOOT_REGION_DESCRIPTOR_SIZE - 1] ^= 0xFF;
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&corrupt),
+            Err(ParseError::InvalidHeaderCrc { .. })
+        ));
+
+        assert!(matches!(
+            BootableRegionDescriptors::from_bytes(&[0xFF; 128]),
+            Err(ParseError::ErasedRegion)
+        ));
+    }
 }
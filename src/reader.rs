@@ -0,0 +1,131 @@
+//! Reading and writing descriptors through a driver instead of dereferencing memory mapped flash.
+//!
+//! Memory mapped reads stop working when the flash controller is reconfigured, or aren't available at all on parts that
+//! reach flash over SPI. FlashRead lets integrators pull the header and app descriptors through their own driver, and
+//! FlashWrite lets UpdateTransaction program them back.
+
+use crate::{AppImageDescriptor, ParseError, APP_IMAGE_DESCRIPTOR_SIZE};
+
+/// Source of descriptor bytes, addressed the same way as the addresses stored in the descriptors
+pub trait FlashRead {
+    /// Driver specific read failure, reported as ParseError::FlashReadFailed
+    type Error;
+
+    /// Fill buf with the bytes starting at addr
+    fn read(&self, addr: u32, buf: &mut [u8]) -> Result<(), Self::Error>;
+}
+
+/// A slice backed reader where addresses are byte offsets into the slice, mainly for tests and host tooling
+impl FlashRead for &[u8] {
+    type Error = ParseError;
+
+    fn read(&self, addr: u32, buf: &mut [u8]) -> Result<(), Self::Error> {
+        let needed = (addr as usize)
+            .checked_add(buf.len())
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        let bytes = self.get(addr as usize..needed).ok_or(ParseError::BufferTooSmall {
+            needed,
+            got: self.len(),
+        })?;
+
+        buf.copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// An array backed reader where addresses are byte offsets into the array, see the &\[u8\] reader
+impl<const N: usize> FlashRead for [u8; N] {
+    type Error = ParseError;
+
+    fn read(&self, addr: u32, buf: &mut [u8]) -> Result<(), Self::Error> {
+        self.as_slice().read(addr, buf)
+    }
+}
+
+/// Sink for descriptor bytes, addressed the same way as FlashRead
+pub trait FlashWrite {
+    /// Driver specific write failure, reported as ParseError::FlashWriteFailed
+    type Error;
+
+    /// Program data starting at addr. Erasing first, on parts that need it, is up to the driver.
+    fn write(&mut self, addr: u32, data: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// A slice backed writer where addresses are byte offsets into the slice, mainly for tests and host tooling
+impl FlashWrite for &mut [u8] {
+    type Error = ParseError;
+
+    fn write(&mut self, addr: u32, data: &[u8]) -> Result<(), Self::Error> {
+        let got = self.len();
+        let needed = (addr as usize)
+            .checked_add(data.len())
+            .ok_or(ParseError::ArithmeticOverflow)?;
+        let bytes = self
+            .get_mut(addr as usize..needed)
+            .ok_or(ParseError::BufferTooSmall { needed, got })?;
+
+        bytes.copy_from_slice(data);
+        Ok(())
+    }
+}
+
+/// An array backed writer where addresses are byte offsets into the array, see the &mut \[u8\] writer
+impl<const N: usize> FlashWrite for [u8; N] {
+    type Error = ParseError;
+
+    fn write(&mut self, addr: u32, data: &[u8]) -> Result<(), Self::Error> {
+        self.as_mut_slice().write(addr, data)
+    }
+}
+
+/// Object safe FlashRead with the driver error erased, so BootableRegionDescriptors doesn't need a reader type parameter
+pub(crate) trait ErasedFlashRead {
+    fn read_erased(&self, addr: u32, buf: &mut [u8]) -> Result<(), ParseError>;
+}
+
+impl<R: FlashRead> ErasedFlashRead for R {
+    fn read_erased(&self, addr: u32, buf: &mut [u8]) -> Result<(), ParseError> {
+        self.read(addr, buf)
+            .map_err(|_| ParseError::FlashReadFailed { address: addr })
+    }
+}
+
+/// FlashWrite with the driver error erased, see ErasedFlashRead
+pub(crate) trait ErasedFlashWrite {
+    fn write_erased(&mut self, addr: u32, data: &[u8]) -> Result<(), ParseError>;
+}
+
+impl<W: FlashWrite> ErasedFlashWrite for W {
+    fn write_erased(&mut self, addr: u32, data: &[u8]) -> Result<(), ParseError> {
+        self.write(addr, data)
+            .map_err(|_| ParseError::FlashWriteFailed { address: addr })
+    }
+}
+
+/// Read and validate app descriptor slot of the array at base_address through reader
+pub(crate) fn read_app(
+    reader: &dyn ErasedFlashRead,
+    base_address: u32,
+    slot: u32,
+) -> Result<AppImageDescriptor, ParseError> {
+    let address = slot
+        .checked_mul(APP_IMAGE_DESCRIPTOR_SIZE as u32)
+        .and_then(|offset| offset.checked_add(base_address))
+        .ok_or(ParseError::ArithmeticOverflow)?;
+
+    let mut buf = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+    reader.read_erased(address, &mut buf)?;
+
+    AppImageDescriptor::from_encoded_bytes(&buf, cfg!(target_endian = "big"), Some(slot)).map_err(|err| match err {
+        // report where the descriptor lives on flash rather than the stack copy
+        ParseError::InvalidAppCrc {
+            slot, found, expected, ..
+        } => ParseError::InvalidAppCrc {
+            slot,
+            address: address as usize,
+            found,
+            expected,
+        },
+        err => err,
+    })
+}
@@ -0,0 +1,296 @@
+//! Coordinating an update of an inactive slot in a descriptor region.
+//!
+//! Every update handler runs the same sequence: write the new image to an inactive slot's partition, write its descriptor,
+//! check the image against its CRC, then make the slot active and recompute header_crc. UpdateTransaction enforces that
+//! order over a region buffer, writing each change through a FlashWrite writer and reading the image back through a
+//! FlashRead reader, so the active slot can only change once the staged image has been verified.
+
+use core::mem::size_of;
+
+use crate::reader::{ErasedFlashRead, ErasedFlashWrite};
+use crate::{
+    AppImageDescriptor, BootableRegionDescriptorHeader, BootableRegionDescriptors, FlashRead, FlashWrite, ParseError,
+    APP_IMAGE_DESCRIPTOR_SIZE, BOOT_REGION_DESCRIPTOR_SIZE,
+};
+
+/// How far an UpdateTransaction has progressed
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Step {
+    /// Nothing staged yet
+    Open,
+    /// A descriptor has been written for this slot but its image hasn't been verified
+    Staged(u32),
+    /// The image staged in this slot matches its CRC, so the slot can be made active
+    Verified(u32),
+}
+
+/// Stages a new image descriptor into an inactive slot of a region laid out as for BootableRegionDescriptors::from_bytes,
+/// and only makes it active once the image has been verified. region is a copy of the region programmed at region_address
+/// on flash: every change is written to flash through a FlashWrite writer first, and only applied to region once that
+/// write succeeds. Writing the image itself before verify is up to the caller.
+pub struct UpdateTransaction<'a> {
+    region: &'a mut [u8],
+    region_address: u32,
+    step: Step,
+}
+
+impl<'a> UpdateTransaction<'a> {
+    /// Begin an update of region, a copy of the region at region_address on flash, which must already hold a valid set of
+    /// descriptors
+    pub fn new(region: &'a mut [u8], region_address: u32) -> Result<Self, ParseError> {
+        BootableRegionDescriptors::from_bytes(region)?;
+
+        Ok(Self {
+            region,
+            region_address,
+            step: Step::Open,
+        })
+    }
+
+    /// Write descriptor into slot through flash, which must be inactive and match descriptor's app_slot_number. The
+    /// descriptor is fully validated before anything is written. Staging again replaces any earlier staged descriptor and
+    /// its verification. If the write fails nothing is left staged, and region is unchanged.
+    pub fn stage<W: FlashWrite>(
+        &mut self,
+        flash: &mut W,
+        slot: u32,
+        descriptor: AppImageDescriptor,
+    ) -> Result<(), ParseError> {
+        let header = BootableRegionDescriptorHeader::from_bytes(self.region)?;
+        let offset = header.descriptor_offset(slot)?;
+        if slot == header.active_app_slot {
+            return Err(ParseError::ActiveSlotStaged { slot });
+        }
+        if descriptor.app_slot_number != slot {
+            return Err(ParseError::SlotNumberMismatch {
+                index: slot,
+                declared: descriptor.app_slot_number,
+            });
+        }
+
+        let mut encoded = [0u8; APP_IMAGE_DESCRIPTOR_SIZE];
+        descriptor.write_to(&mut encoded)?;
+        AppImageDescriptor::from_encoded_bytes(&encoded, cfg!(target_endian = "big"), Some(slot))?;
+
+        let got = self.region.len();
+        let bytes = offset
+            .checked_add(APP_IMAGE_DESCRIPTOR_SIZE)
+            .and_then(|end| self.region.get_mut(offset..end))
+            .ok_or(ParseError::BufferTooSmall {
+                needed: offset.saturating_add(APP_IMAGE_DESCRIPTOR_SIZE),
+                got,
+            })?;
+        let address = u32::try_from(offset)
+            .ok()
+            .and_then(|offset| self.region_address.checked_add(offset))
+            .ok_or(ParseError::ArithmeticOverflow)?;
+
+        // a failed write may have left the slot half programmed, so whatever was staged before no longer holds
+        self.step = Step::Open;
+        flash.write_erased(address, &encoded)?;
+        bytes.copy_from_slice(&encoded);
+
+        self.step = Step::Staged(slot);
+        Ok(())
+    }
+
+    /// Check the staged image against the CRC at its stored_crc_address, reading both through flash at the addresses in
+    /// the staged descriptor. Only INTEGRITY_CRC32 images can be verified. Always succeeds if
+    /// APP_IMAGE_FLAG_SKIP_IMAGE_CRC_CHECK is set. Fails with UpdateOutOfOrder
+    /// if nothing has been staged.
+    pub fn verify<R: FlashRead>(&mut self, flash: &R) -> Result<(), ParseError> {
+        let slot = match self.step {
+            Step::Staged(slot) | Step::Verified(slot) => slot,
+            Step::Open => return Err(ParseError::UpdateOutOfOrder),
+        };
+        // a failed verify leaves the slot staged but not verified
+        self.step = Step::Staged(slot);
+
+        let header = BootableRegionDescriptorHeader::from_bytes(self.region)?;
+        let offset = header.descriptor_offset(slot)?;
+        // can't index out of range as stage wrote the descriptor at offset
+        let descriptor = AppImageDescriptor::from_bytes(&self.region[offset..])?;
+
+        if !descriptor.is_skip_crc_check() {
+            let mut expected = [0u8; size_of::<u32>()];
+            flash.read_erased(descriptor.stored_crc_address, &mut expected)?;
+
+            let mut digest = descriptor.image_digest();
+            let mut chunk = [0u8; 64];
+            let mut address = descriptor.stored_address;
+            let mut remaining = descriptor.image_size_bytes;
+            while remaining > 0 {
+                let len = remaining.min(chunk.len() as u32);
+                flash.read_erased(address, &mut chunk[..len as usize])?;
+                digest.update(&chunk[..len as usize]);
+                address = address.checked_add(len).ok_or(ParseError::ArithmeticOverflow)?;
+                remaining -= len;
+            }
+
+            descriptor.finish_image_digest(digest, u32::from_le_bytes(expected))?;
+        }
+
+        self.step = Step::Verified(slot);
+        Ok(())
+    }
+
+    /// Make the verified slot active and recompute header_crc, writing the header through flash, ending the transaction.
+    /// Fails with UpdateOutOfOrder, leaving the active slot unchanged, unless the most recently staged image passed verify.
+    /// If the write fails region is unchanged.
+    pub fn commit<W: FlashWrite>(self, flash: &mut W) -> Result<(), ParseError> {
+        let Step::Verified(slot) = self.step else {
+            return Err(ParseError::UpdateOutOfOrder);
+        };
+
+        let mut header = [0u8; BOOT_REGION_DESCRIPTOR_SIZE];
+        // can't index out of range as new checked that region holds a valid header
+        header.copy_from_slice(&self.region[..BOOT_REGION_DESCRIPTOR_SIZE]);
+        BootableRegionDescriptors::set_active_slot_in_buffer(&mut header, slot)?;
+
+        flash.write_erased(self.region_address, &header)?;
+        self.region[..BOOT_REGION_DESCRIPTOR_SIZE].copy_from_slice(&header);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+    use crate::APP_IMAGE_FLAG_NONE;
+
+    const REGION_SIZE: usize = BOOT_REGION_DESCRIPTOR_SIZE + 2 * APP_IMAGE_DESCRIPTOR_SIZE;
+
+    /// Two slot region with slot 0 active
+    fn region() -> [u8; REGION_SIZE] {
+        let header = BootableRegionDescriptorHeader::new(2, 0, BOOT_REGION_DESCRIPTOR_SIZE as u32);
+        crate::build_region(
+            header,
+            &[
+                AppImageDescriptor::new_execute_in_place_image(0, 1, 0, APP_IMAGE_FLAG_NONE, 0x1000, 0x100, 0x1100),
+                AppImageDescriptor::new_execute_in_place_image(1, 1, 0, APP_IMAGE_FLAG_NONE, 0x2000, 0x100, 0x2100),
+            ],
+        )
+    }
+
+    /// Flash holding a 0x100 byte image at 0x10 and its CRC at 0x110
+    fn flash(descriptor: &AppImageDescriptor) -> [u8; 0x114] {
+        let mut flash = [0u8; 0x114];
+        for (i, byte) in flash[0x10..0x110].iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let mut digest = descriptor.image_digest();
+        digest.update(&flash[0x10..0x110]);
+        flash[0x110..].copy_from_slice(&digest.finalize().to_le_bytes());
+        flash
+    }
+
+    /// Writer whose driver always fails
+    struct FailingWriter;
+
+    impl FlashWrite for FailingWriter {
+        type Error = ();
+
+        fn write(&mut self, _addr: u32, _data: &[u8]) -> Result<(), Self::Error> {
+            Err(())
+        }
+    }
+
+    #[test]
+    fn stage_verify_commit() {
+        let mut region = region();
+        let update = AppImageDescriptor::new_execute_in_place_image(1, 2, 0, APP_IMAGE_FLAG_NONE, 0x10, 0x100, 0x110);
+        let flash = flash(&update);
+        // the region is programmed at 0x40 on the flash being written
+        let mut written = [0u8; 0x40 + REGION_SIZE];
+        written[0x40..].copy_from_slice(&region);
+
+        let mut transaction = UpdateTransaction::new(&mut region, 0x40).unwrap();
+        assert!(matches!(transaction.verify(&flash), Err(ParseError::UpdateOutOfOrder)));
+        assert!(matches!(
+            transaction.stage(&mut written, 0, update),
+            Err(ParseError::ActiveSlotStaged { slot: 0 })
+        ));
+        assert!(transaction.stage(&mut written, 1, update).is_ok());
+        assert!(transaction.verify(&flash).is_ok());
+        assert!(transaction.commit(&mut written).is_ok());
+
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!(descriptors.active_slot_index(), 1);
+        assert_eq!(descriptors.get_active_slot(), update);
+        assert_eq!(written[0x40..], region);
+    }
+
+    #[test]
+    fn failed_verify_does_not_commit() {
+        let mut region = region();
+        let update = AppImageDescriptor::new_execute_in_place_image(1, 2, 0, APP_IMAGE_FLAG_NONE, 0x10, 0x100, 0x110);
+        let mut flash = flash(&update);
+        flash[0x20] ^= 1;
+        let mut written = region;
+
+        let mut transaction = UpdateTransaction::new(&mut region, 0).unwrap();
+        assert!(matches!(
+            transaction.stage(
+                &mut written,
+                1,
+                AppImageDescriptor {
+                    app_slot_number: 0,
+                    ..update
+                }
+            ),
+            Err(ParseError::SlotNumberMismatch { index: 1, declared: 0 })
+        ));
+        assert!(transaction.commit(&mut written).is_err());
+
+        let mut transaction = UpdateTransaction::new(&mut region, 0).unwrap();
+        assert!(transaction.stage(&mut written, 1, update).is_ok());
+        assert!(matches!(
+            transaction.verify(&flash),
+            Err(ParseError::InvalidImageCrc { .. })
+        ));
+        assert!(matches!(
+            transaction.commit(&mut written),
+            Err(ParseError::UpdateOutOfOrder)
+        ));
+
+        // the descriptor was staged, but slot 0 is still the one booted
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!(descriptors.active_slot_index(), 0);
+        assert_eq!(descriptors.get_app_at_slot(1).unwrap(), update);
+
+        // only CRC32 images can be verified
+        let mut transaction = UpdateTransaction::new(&mut region, 0).unwrap();
+        let mut sha256 = update;
+        sha256.integrity_type = crate::INTEGRITY_SHA256;
+        sha256.recompute_crc();
+        assert!(transaction.stage(&mut written, 1, sha256).is_ok());
+        assert!(matches!(
+            transaction.verify(&flash),
+            Err(ParseError::UnsupportedIntegrityType { slot: 1, .. })
+        ));
+
+        // a failed descriptor write leaves nothing staged and region as it was
+        flash[0x20] ^= 1;
+        let mut transaction = UpdateTransaction::new(&mut region, 0).unwrap();
+        let slot_1 = (BOOT_REGION_DESCRIPTOR_SIZE + APP_IMAGE_DESCRIPTOR_SIZE) as u32;
+        assert!(matches!(
+            transaction.stage(&mut FailingWriter, 1, update),
+            Err(ParseError::FlashWriteFailed { address }) if address == slot_1
+        ));
+        assert!(matches!(transaction.verify(&flash), Err(ParseError::UpdateOutOfOrder)));
+
+        // a failed header write doesn't flip the active slot
+        assert!(transaction.stage(&mut written, 1, update).is_ok());
+        assert!(transaction.verify(&flash).is_ok());
+        assert!(matches!(
+            transaction.commit(&mut FailingWriter),
+            Err(ParseError::FlashWriteFailed { address: 0 })
+        ));
+        let descriptors = BootableRegionDescriptors::from_bytes(&region).unwrap();
+        assert_eq!(descriptors.active_slot_index(), 0);
+        assert_eq!(
+            written[..BOOT_REGION_DESCRIPTOR_SIZE],
+            region[..BOOT_REGION_DESCRIPTOR_SIZE]
+        );
+    }
+}